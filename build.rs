@@ -0,0 +1,164 @@
+//! Decodes the raster images under `assets/images` at build time, so the
+//! site ships plain RGBA pixels instead of an image decoder, and converts
+//! the books under `assets/books` from Markdown or HTML to the markup pages
+//! are written in.
+
+use std::{env, fs, path::Path};
+
+use image::imageops::FilterType;
+
+#[path = "src/chapter.rs"]
+mod chapter;
+
+/// Images wider than this are downscaled, nobody has a terminal that wide.
+const MAX_WIDTH: u32 = 160;
+
+fn main() {
+    let out_dir = env::var("OUT_DIR").expect("cargo sets OUT_DIR");
+    let images_dir = Path::new("assets/images");
+
+    println!("cargo:rerun-if-changed={}", images_dir.display());
+
+    let mut paths: Vec<_> = fs::read_dir(images_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.extension()
+                        .and_then(|extension| extension.to_str())
+                        .is_some_and(|extension| {
+                            matches!(
+                                extension.to_ascii_lowercase().as_str(),
+                                "png" | "jpg" | "jpeg"
+                            )
+                        })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    paths.sort();
+
+    let mut images = String::from("pub const IMAGES: &[RasterImage] = &[\n");
+
+    for path in paths {
+        println!("cargo:rerun-if-changed={}", path.display());
+
+        let name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .expect("image names are valid UTF-8");
+
+        let mut decoded = image::open(&path)
+            .unwrap_or_else(|error| panic!("cannot decode {}: {error}", path.display()));
+
+        if decoded.width() > MAX_WIDTH {
+            let height = decoded.height() * MAX_WIDTH / decoded.width();
+            decoded = decoded.resize_exact(MAX_WIDTH, height.max(1), FilterType::Triangle);
+        }
+
+        let rgba = decoded.to_rgba8();
+        let pixels_path = Path::new(&out_dir).join(format!("{name}.rgba"));
+        fs::write(&pixels_path, rgba.as_raw()).expect("OUT_DIR is writable");
+
+        images.push_str(&format!(
+            "    RasterImage {{ name: {name:?}, width: {}, height: {}, pixels: include_bytes!({:?}) }},\n",
+            rgba.width(),
+            rgba.height(),
+            pixels_path.display().to_string(),
+        ));
+    }
+
+    images.push_str("];\n");
+
+    fs::write(Path::new(&out_dir).join("images.rs"), images).expect("OUT_DIR is writable");
+    fs::write(Path::new(&out_dir).join("books.rs"), books()).expect("OUT_DIR is writable");
+}
+
+/// Every directory under `assets/books` is a book: `book.md` holds its
+/// `# Title` and an optional `warning:` line, and each `NN-*.md` or
+/// `NN-*.html` beside it is a chapter, in file name order.
+fn books() -> String {
+    let books_dir = Path::new("assets/books");
+    println!("cargo:rerun-if-changed={}", books_dir.display());
+
+    let mut directories: Vec<_> = fs::read_dir(books_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .collect()
+        })
+        .unwrap_or_default();
+    directories.sort();
+
+    let mut books = String::from("pub const BOOKS: &[Book] = &[\n");
+
+    for directory in directories {
+        println!("cargo:rerun-if-changed={}", directory.display());
+
+        let id = directory
+            .file_name()
+            .and_then(|name| name.to_str())
+            .expect("book names are valid UTF-8");
+        let metadata = fs::read_to_string(directory.join("book.md")).unwrap_or_default();
+        let title = metadata
+            .lines()
+            .find_map(|line| line.strip_prefix("# "))
+            .unwrap_or(id)
+            .trim();
+        let warning = metadata
+            .lines()
+            .find_map(|line| line.strip_prefix("warning:"))
+            .map(str::trim);
+
+        let mut paths: Vec<_> = fs::read_dir(&directory)
+            .expect("book directories are readable")
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(|first: char| first.is_ascii_digit()))
+                    && path
+                        .extension()
+                        .is_some_and(|extension| extension == "md" || extension == "html")
+            })
+            .collect();
+        paths.sort();
+
+        books.push_str(&format!(
+            "    Book {{ id: {id:?}, title: {title:?}, warning: {warning:?}, chapters: &[\n"
+        ));
+
+        for path in paths {
+            println!("cargo:rerun-if-changed={}", path.display());
+
+            let source = fs::read_to_string(&path)
+                .unwrap_or_else(|error| panic!("cannot read {}: {error}", path.display()));
+            let (heading, text) = match path.extension().and_then(|extension| extension.to_str()) {
+                Some("html") => chapter::from_html(&source),
+                _ => chapter::from_markdown(&source),
+            };
+            let title = heading.unwrap_or_else(|| {
+                let stem = path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .unwrap_or_default();
+                stem.trim_start_matches(|letter: char| letter.is_ascii_digit() || letter == '-')
+                    .replace(['-', '_'], " ")
+            });
+            let text = format!(">>> {title}\n{}", text.trim());
+
+            books.push_str(&format!(
+                "        Chapter {{ title: {title:?}, text: {text:?} }},\n"
+            ));
+        }
+
+        books.push_str("    ] },\n");
+    }
+
+    books.push_str("];\n");
+    books
+}
@@ -0,0 +1,163 @@
+//! Achievements for the things visitors dig up on the site.
+//!
+//! Unlocked achievements and the pages read to the bottom are kept in
+//! `localStorage`, and every new unlock pops a short toast.
+
+use web_time::{Duration, Instant};
+
+use crate::storage;
+
+const TOAST_DURATION: Duration = Duration::from_secs(4);
+
+#[derive(Debug)]
+pub struct Achievement {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+}
+
+/// Secrets unlock the achievement sharing their name.
+pub const ACHIEVEMENTS: &[Achievement] = &[
+    Achievement {
+        id: "intro",
+        name: "Patience",
+        description: "Watched the whole intro without pressing a key.",
+    },
+    Achievement {
+        id: "bookworm",
+        name: "Bookworm",
+        description: "Read every page down to the last line.",
+    },
+    Achievement {
+        id: "tarbetu",
+        name: "Bit Flipper",
+        description: "Decoded the binary header, letter by letter.",
+    },
+    Achievement {
+        id: "konami",
+        name: "Thirty Lives",
+        description: "Entered the Konami code. The background noticed.",
+    },
+    Achievement {
+        id: "sudo",
+        name: "Root Access",
+        description: "Typed sudo and found a hidden page.",
+    },
+    Achievement {
+        id: "hjkl",
+        name: "Home Row",
+        description: "Pressed h, j, k and l all at once.",
+    },
+    Achievement {
+        id: "gdb",
+        name: "Post Mortem",
+        description: "Ran a command at the gdb prompt of the core dump.",
+    },
+    Achievement {
+        id: "shell",
+        name: "Shell Escape",
+        description: "Ran a shell command without ever leaving gdb.",
+    },
+];
+
+#[derive(Debug)]
+pub struct Achievements {
+    unlocked: [bool; ACHIEVEMENTS.len()],
+    read_pages: u32,
+    toast: Option<(&'static Achievement, Instant)>,
+}
+
+impl Default for Achievements {
+    fn default() -> Self {
+        let mut unlocked = [false; ACHIEVEMENTS.len()];
+
+        if let Some(saved) = storage::get("achievements") {
+            for (achievement, unlocked) in ACHIEVEMENTS.iter().zip(unlocked.iter_mut()) {
+                *unlocked = saved.split(',').any(|id| id == achievement.id);
+            }
+        }
+
+        Self {
+            unlocked,
+            read_pages: storage::get("read_pages")
+                .and_then(|saved| saved.parse().ok())
+                .unwrap_or_default(),
+            toast: None,
+        }
+    }
+}
+
+impl Achievements {
+    /// Unlocks the achievement with the given id, if there is one and it
+    /// is not unlocked yet.
+    pub fn unlock(&mut self, id: &str, now: Instant) {
+        let Some(index) = ACHIEVEMENTS
+            .iter()
+            .position(|achievement| achievement.id == id)
+        else {
+            return;
+        };
+
+        if self.unlocked[index] {
+            return;
+        }
+
+        self.unlocked[index] = true;
+        self.toast = Some((&ACHIEVEMENTS[index], now));
+
+        let saved = self
+            .entries()
+            .filter(|(_, unlocked)| *unlocked)
+            .map(|(achievement, _)| achievement.id)
+            .collect::<Vec<_>>()
+            .join(",");
+        storage::set("achievements", &saved);
+    }
+
+    /// Records that `page` of `pages` was scrolled to its last line.
+    pub fn read_page(&mut self, page: usize, pages: usize, now: Instant) {
+        let bit = 1 << page;
+
+        if self.read_pages & bit == 0 {
+            self.read_pages |= bit;
+            storage::set("read_pages", &self.read_pages.to_string());
+        }
+
+        if self.read_pages.count_ones() as usize >= pages {
+            self.unlock("bookworm", now);
+        }
+    }
+
+    pub fn toast(&self, now: Instant) -> Option<&'static Achievement> {
+        self.toast
+            .filter(|(_, at)| now.duration_since(*at) < TOAST_DURATION)
+            .map(|(achievement, _)| achievement)
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (&'static Achievement, bool)> + '_ {
+        ACHIEVEMENTS.iter().zip(self.unlocked.iter().copied())
+    }
+
+    /// Contents of the `./achievements` page.
+    pub fn page(&self) -> String {
+        let unlocked = self.unlocked.iter().filter(|unlocked| **unlocked).count();
+
+        let mut page = format!(
+            ">>> ./achievements\n{unlocked} of {} unlocked. The locked ones stay a mystery until you find them.\n",
+            ACHIEVEMENTS.len()
+        );
+
+        for (achievement, unlocked) in self.entries() {
+            if unlocked {
+                page.push_str(&format!(
+                    "\n[x] {}\n    {}\n",
+                    achievement.name, achievement.description
+                ));
+            } else {
+                page.push_str("\n[ ] ???\n    ???\n");
+            }
+        }
+
+        page
+    }
+}
@@ -0,0 +1,368 @@
+//! ANSI art.
+//!
+//! Plays `.ans` files the way a terminal would: bytes are decoded as UTF-8
+//! when they are valid UTF-8 and as CP437 otherwise, SGR escapes set the
+//! pen, cursor escapes move it, and lines wrap at the art's width. The
+//! resulting canvas becomes a styled [`Text`].
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span, Text},
+};
+
+/// The width ANSI art is drawn for unless told otherwise.
+pub const DEFAULT_WIDTH: usize = 80;
+
+/// The SAUCE record some editors append after an end-of-file byte.
+const SAUCE_LENGTH: usize = 128;
+
+/// CP437 glyphs for bytes 0x80 to 0xFF.
+const CP437_HIGH: &str = "ÇüéâäàåçêëèïîìÄÅÉæÆôöòûùÿÖÜ¢£¥₧ƒáíóúñÑªº¿⌐¬½¼¡«»\
+░▒▓│┤╡╢╖╕╣║╗╝╜╛┐└┴┬├─┼╞╟╚╔╩╦╠═╬╧╨╤╥╙╘╒╓╫╪┘┌█▄▌▐▀\
+αßΓπΣσµτΦΘΩδ∞φε∩≡±≥≤⌠⌡÷≈°∙·√ⁿ²■\u{a0}";
+
+/// CP437 glyphs for the control bytes 0x01 to 0x1F, which ANSI art draws
+/// instead of obeying.
+const CP437_LOW: &str = "☺☻♥♦♣♠•◘○◙♂♀♪♫☼►◄↕‼¶§▬↨↑↓→←∟↔▲▼";
+
+/// The sixteen colors of the classic palette, in SGR order.
+const PALETTE: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Gray,
+    Color::DarkGray,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::White,
+];
+
+/// Decodes `bytes` and draws them on a canvas of the given width.
+pub fn parse(bytes: &[u8], width: usize) -> Text<'static> {
+    let bytes = strip_sauce(bytes);
+    let mut canvas = Canvas::new(width.max(1));
+
+    match std::str::from_utf8(bytes) {
+        Ok(text) => canvas.play(text.chars(), false),
+        Err(_) => canvas.play(bytes.iter().map(|byte| cp437(*byte)), true),
+    }
+
+    canvas.into_text()
+}
+
+fn cp437(byte: u8) -> char {
+    match byte {
+        0x80.. => CP437_HIGH
+            .chars()
+            .nth(usize::from(byte - 0x80))
+            .unwrap_or('?'),
+        _ => char::from(byte),
+    }
+}
+
+/// Everything from the end-of-file marker on, which covers SAUCE records
+/// and their comments.
+fn strip_sauce(bytes: &[u8]) -> &[u8] {
+    let has_sauce =
+        bytes.len() >= SAUCE_LENGTH && bytes[bytes.len() - SAUCE_LENGTH..].starts_with(b"SAUCE00");
+
+    match bytes.iter().position(|byte| *byte == 0x1A) {
+        Some(end) => &bytes[..end],
+        None if has_sauce => &bytes[..bytes.len() - SAUCE_LENGTH],
+        None => bytes,
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Pen {
+    foreground: Option<Color>,
+    background: Option<Color>,
+    /// Index into [`PALETTE`] of a basic foreground color, kept so bold
+    /// can brighten it the way DOS did.
+    basic_foreground: Option<usize>,
+    modifier: Modifier,
+}
+
+impl Pen {
+    fn style(self) -> Style {
+        let foreground = match self.basic_foreground {
+            Some(index) if self.modifier.contains(Modifier::BOLD) => Some(PALETTE[index + 8]),
+            Some(index) => Some(PALETTE[index]),
+            None => self.foreground,
+        };
+
+        let mut style = Style::default().add_modifier(self.modifier);
+        style.fg = foreground;
+        style.bg = self.background;
+        style
+    }
+
+    fn apply(&mut self, parameters: &[u16]) {
+        let mut parameters = parameters.iter().copied();
+
+        while let Some(parameter) = parameters.next() {
+            match parameter {
+                0 => *self = Pen::default(),
+                1 => self.modifier.insert(Modifier::BOLD),
+                2 => self.modifier.insert(Modifier::DIM),
+                3 => self.modifier.insert(Modifier::ITALIC),
+                4 => self.modifier.insert(Modifier::UNDERLINED),
+                5 | 6 => self.modifier.insert(Modifier::SLOW_BLINK),
+                7 => self.modifier.insert(Modifier::REVERSED),
+                8 => self.modifier.insert(Modifier::HIDDEN),
+                9 => self.modifier.insert(Modifier::CROSSED_OUT),
+                22 => self.modifier.remove(Modifier::BOLD | Modifier::DIM),
+                23 => self.modifier.remove(Modifier::ITALIC),
+                24 => self.modifier.remove(Modifier::UNDERLINED),
+                25 => self.modifier.remove(Modifier::SLOW_BLINK),
+                27 => self.modifier.remove(Modifier::REVERSED),
+                28 => self.modifier.remove(Modifier::HIDDEN),
+                29 => self.modifier.remove(Modifier::CROSSED_OUT),
+                30..=37 => self.set_foreground(usize::from(parameter - 30)),
+                90..=97 => self.set_foreground(usize::from(parameter - 90 + 8)),
+                40..=47 => self.background = Some(PALETTE[usize::from(parameter - 40)]),
+                100..=107 => self.background = Some(PALETTE[usize::from(parameter - 100 + 8)]),
+                38 => {
+                    self.basic_foreground = None;
+                    self.foreground = extended_color(&mut parameters);
+                }
+                48 => self.background = extended_color(&mut parameters),
+                39 => {
+                    self.basic_foreground = None;
+                    self.foreground = None;
+                }
+                49 => self.background = None,
+                _ => {}
+            }
+        }
+    }
+
+    fn set_foreground(&mut self, index: usize) {
+        if index < 8 {
+            self.basic_foreground = Some(index);
+        } else {
+            self.basic_foreground = None;
+            self.foreground = Some(PALETTE[index]);
+        }
+    }
+}
+
+/// Reads the rest of a `38;5;n` or `38;2;r;g;b` color.
+fn extended_color(parameters: &mut impl Iterator<Item = u16>) -> Option<Color> {
+    let mut next = || parameters.next().map(|value| value.min(255) as u8);
+
+    match next()? {
+        5 => next().map(Color::Indexed),
+        2 => Some(Color::Rgb(next()?, next()?, next()?)),
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+struct Canvas {
+    width: usize,
+    rows: Vec<Vec<(char, Style)>>,
+    x: usize,
+    y: usize,
+    saved: (usize, usize),
+    pen: Pen,
+}
+
+impl Canvas {
+    fn new(width: usize) -> Self {
+        Self {
+            width,
+            rows: Vec::new(),
+            x: 0,
+            y: 0,
+            saved: (0, 0),
+            pen: Pen::default(),
+        }
+    }
+
+    /// Interprets the characters. With `control_glyphs`, control bytes
+    /// other than the ones that move the cursor are drawn as CP437 glyphs.
+    fn play(&mut self, chars: impl Iterator<Item = char>, control_glyphs: bool) {
+        let mut chars = chars.peekable();
+
+        while let Some(letter) = chars.next() {
+            match letter {
+                '\x1b' if chars.peek() == Some(&'[') => {
+                    chars.next();
+
+                    let mut sequence = String::new();
+                    for letter in chars.by_ref() {
+                        if ('\x40'..='\x7e').contains(&letter) {
+                            self.escape(&sequence, letter);
+                            break;
+                        }
+                        sequence.push(letter);
+                    }
+                }
+                '\n' => {
+                    self.x = 0;
+                    self.y += 1;
+                }
+                '\r' => self.x = 0,
+                '\t' => self.x = (self.x / 8 + 1) * 8,
+                '\x01'..='\x1f' if control_glyphs => {
+                    let glyph = CP437_LOW.chars().nth(letter as usize - 1);
+                    self.put(glyph.unwrap_or(' '));
+                }
+                letter if letter.is_control() => {}
+                letter => self.put(letter),
+            }
+        }
+    }
+
+    fn escape(&mut self, sequence: &str, command: char) {
+        let parameters: Vec<u16> = sequence
+            .trim_start_matches('?')
+            .split(';')
+            .map(|parameter| parameter.parse().unwrap_or(0))
+            .collect();
+        let count = usize::from(parameters.first().copied().unwrap_or(0).max(1));
+
+        match command {
+            'm' => self.pen.apply(&parameters),
+            'A' => self.y = self.y.saturating_sub(count),
+            'B' => self.y += count,
+            'C' => self.x = (self.x + count).min(self.width - 1),
+            'D' => self.x = self.x.saturating_sub(count),
+            'H' | 'f' => {
+                let position = |index: usize| {
+                    usize::from(parameters.get(index).copied().unwrap_or(1).max(1)) - 1
+                };
+                self.y = position(0);
+                self.x = position(1).min(self.width - 1);
+            }
+            's' => self.saved = (self.x, self.y),
+            'u' => (self.x, self.y) = self.saved,
+            'J' if parameters.first() == Some(&2) => {
+                self.rows.clear();
+                (self.x, self.y) = (0, 0);
+            }
+            'K' => {
+                if let Some(row) = self.rows.get_mut(self.y) {
+                    row.truncate(self.x);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn put(&mut self, letter: char) {
+        if self.x >= self.width {
+            self.x = 0;
+            self.y += 1;
+        }
+
+        if self.rows.len() <= self.y {
+            self.rows.resize(self.y + 1, Vec::new());
+        }
+
+        let row = &mut self.rows[self.y];
+        if row.len() <= self.x {
+            row.resize(self.x + 1, (' ', Style::default()));
+        }
+        row[self.x] = (letter, self.pen.style());
+
+        self.x += 1;
+    }
+
+    fn into_text(self) -> Text<'static> {
+        let lines = self.rows.into_iter().map(|row| {
+            let mut spans: Vec<Span<'static>> = Vec::new();
+            let mut content = String::new();
+            let mut current = None;
+
+            for (letter, style) in row {
+                if let Some(previous) = current.filter(|previous| *previous != style) {
+                    spans.push(Span::styled(std::mem::take(&mut content), previous));
+                }
+                current = Some(style);
+                content.push(letter);
+            }
+
+            if let Some(style) = current {
+                spans.push(Span::styled(content, style));
+            }
+
+            Line::from(spans)
+        });
+
+        Text::from_iter(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(text: &Text) -> Vec<String> {
+        text.lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn bytes_that_are_not_utf8_are_cp437() {
+        let text = parse(b"\xb0\xdb\x03", DEFAULT_WIDTH);
+
+        assert_eq!(rows(&text), ["░█♥"]);
+    }
+
+    #[test]
+    fn lines_wrap_at_the_width_of_the_art() {
+        let text = parse(b"abcdef", 4);
+
+        assert_eq!(rows(&text), ["abcd", "ef"]);
+    }
+
+    #[test]
+    fn bold_brightens_basic_colors() {
+        let text = parse(b"\x1b[31mr\x1b[1mR\x1b[0mn", DEFAULT_WIDTH);
+        let spans = &text.lines[0].spans;
+
+        assert_eq!(spans[0].style.fg, Some(Color::Red));
+        assert_eq!(spans[1].style.fg, Some(Color::LightRed));
+        assert_eq!(spans[2].style, Style::default());
+    }
+
+    #[test]
+    fn extended_colors_are_read_whole() {
+        let text = parse(b"\x1b[38;2;1;2;3mx\x1b[48;5;200my", DEFAULT_WIDTH);
+        let spans = &text.lines[0].spans;
+
+        assert_eq!(spans[0].style.fg, Some(Color::Rgb(1, 2, 3)));
+        assert_eq!(spans[1].style.bg, Some(Color::Indexed(200)));
+    }
+
+    #[test]
+    fn the_cursor_moves_where_it_is_told() {
+        let text = parse(b"\x1b[2;3Hx\x1b[1;1Hy", DEFAULT_WIDTH);
+
+        assert_eq!(rows(&text), ["y", "  x"]);
+    }
+
+    #[test]
+    fn nothing_after_the_end_of_file_is_drawn() {
+        let text = parse(b"art\x1aSAUCE00 comments", DEFAULT_WIDTH);
+
+        assert_eq!(rows(&text), ["art"]);
+    }
+}
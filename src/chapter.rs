@@ -0,0 +1,397 @@
+//! Book chapters, converted from Markdown or HTML to the markup pages are
+//! written in.
+//!
+//! `build.rs` runs the conversion on every chapter under `assets/books`;
+//! the site itself only compiles this module for its tests.
+
+/// Converts a Markdown chapter, returning its `# ` heading separately.
+/// Paragraphs are joined into single lines for the pane to wrap, other
+/// headings become `>>>` lines, and emphasis is dropped since the pane has
+/// no use for it.
+pub fn from_markdown(source: &str) -> (Option<String>, String) {
+    let mut heading = None;
+    let mut text = String::new();
+    let mut in_code = false;
+    let mut in_paragraph = false;
+
+    for line in source.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+            end_paragraph(&mut text, &mut in_paragraph);
+            text.push_str(trimmed);
+            text.push('\n');
+            continue;
+        }
+
+        if in_code {
+            text.push_str(line);
+            text.push('\n');
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            if in_paragraph {
+                text.push_str("\n\n");
+                in_paragraph = false;
+            }
+            continue;
+        }
+
+        if let Some(title) = trimmed.strip_prefix("# ") {
+            if heading.is_none() {
+                heading = Some(inline_markdown(title));
+                continue;
+            }
+        }
+
+        let level = trimmed.chars().take_while(|letter| *letter == '#').count();
+        if level > 0 && trimmed[level..].starts_with(' ') {
+            end_paragraph(&mut text, &mut in_paragraph);
+            text.push_str(&format!(">>> {}\n", inline_markdown(&trimmed[level..])));
+            continue;
+        }
+
+        if matches!(trimmed, "---" | "***" | "* * *") {
+            end_paragraph(&mut text, &mut in_paragraph);
+            text.push_str("* * *\n\n");
+            continue;
+        }
+
+        // Lists, quotes and tables keep their lines.
+        let kept = trimmed.starts_with("- ")
+            || trimmed.starts_with("* ")
+            || trimmed.starts_with('|')
+            || trimmed.starts_with('>')
+            || trimmed
+                .split_once(". ")
+                .is_some_and(|(number, _)| number.parse::<u32>().is_ok());
+        let hard_break = line.ends_with("  ");
+
+        if kept {
+            if in_paragraph {
+                text.push('\n');
+            }
+            let item = match trimmed.strip_prefix("* ") {
+                Some(item) => format!("- {item}"),
+                None => trimmed.to_string(),
+            };
+            text.push_str(&inline_markdown(&item));
+            text.push('\n');
+            in_paragraph = false;
+            continue;
+        }
+
+        if in_paragraph {
+            text.push(' ');
+        }
+        text.push_str(&inline_markdown(trimmed));
+        in_paragraph = true;
+
+        if hard_break {
+            text.push('\n');
+            in_paragraph = false;
+        }
+    }
+
+    (heading, text)
+}
+
+/// Drops the markers of paired emphasis and spells links out as
+/// `text (url)`. Code spans are kept as written, backticks and all.
+fn inline_markdown(source: &str) -> String {
+    let mut text = String::new();
+    let mut rest = source.trim();
+
+    while let Some(start) = rest.find('[') {
+        let link = rest[start..].split_once("](").and_then(|(label, after)| {
+            let (url, rest) = after.split_once(')')?;
+            Some((&label[1..], url, rest))
+        });
+
+        match link {
+            Some((label, url, after)) => {
+                text.push_str(&rest[..start]);
+                text.push_str(&format!("{label} ({url})"));
+                rest = after;
+            }
+            None => {
+                text.push_str(&rest[..=start]);
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    text.push_str(rest);
+
+    let mut plain = String::new();
+    let mut rest = text.as_str();
+    while let Some((start, end)) = rest.find('`').and_then(|start| {
+        let end = rest[start + 1..].find('`')? + start + 1;
+        Some((start, end))
+    }) {
+        plain.push_str(&without_emphasis(&rest[..start]));
+        plain.push_str(&rest[start..=end]);
+        rest = &rest[end + 1..];
+    }
+    plain.push_str(&without_emphasis(rest));
+    plain
+}
+
+fn without_emphasis(text: &str) -> String {
+    ["**", "__", "*", "_"]
+        .into_iter()
+        .fold(text.to_string(), |text, marker| strip_pairs(&text, marker))
+}
+
+/// Drops `marker` where it opens and closes a span, the way Markdown
+/// reads emphasis: an opening marker is followed by a letter and a
+/// closing one preceded by one, and underscores inside a word, as in
+/// `snake_case`, are not markers at all.
+fn strip_pairs(text: &str, marker: &str) -> String {
+    let in_words = marker.starts_with('_');
+    let mut stripped = String::new();
+    let mut rest = text;
+
+    while let Some(start) = rest.find(marker) {
+        let before = rest[..start].chars().next_back();
+        let inner = &rest[start + marker.len()..];
+        let opens = inner.starts_with(|letter: char| !letter.is_whitespace())
+            && !(in_words && before.is_some_and(char::is_alphanumeric));
+        let end = inner.match_indices(marker).map(|(end, _)| end).find(|end| {
+            *end > 0
+                && !inner[..*end].ends_with(char::is_whitespace)
+                && !(in_words && inner[end + marker.len()..].starts_with(char::is_alphanumeric))
+        });
+
+        match end.filter(|_| opens) {
+            Some(end) => {
+                stripped.push_str(&rest[..start]);
+                stripped.push_str(&inner[..end]);
+                rest = &inner[end + marker.len()..];
+            }
+            None => {
+                stripped.push_str(&rest[..start + marker.len()]);
+                rest = inner;
+            }
+        }
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+fn end_paragraph(text: &mut String, in_paragraph: &mut bool) {
+    if *in_paragraph {
+        text.push_str("\n\n");
+        *in_paragraph = false;
+    }
+}
+
+/// Converts an HTML chapter, returning its `<h1>` separately. Only the
+/// tags that shape prose are understood, the rest are dropped along with
+/// scripts and styles.
+pub fn from_html(source: &str) -> (Option<String>, String) {
+    let mut heading = None;
+    let mut text = String::new();
+    let mut rest = source;
+    let mut in_pre = false;
+    let mut in_title = false;
+    let mut title = String::new();
+    let mut skipping = None;
+
+    while !rest.is_empty() {
+        let (content, tag, after) = match rest.find('<') {
+            Some(start) => match rest[start..].find('>') {
+                Some(end) => (
+                    &rest[..start],
+                    Some(&rest[start + 1..start + end]),
+                    &rest[start + end + 1..],
+                ),
+                None => (rest, None, ""),
+            },
+            None => (rest, None, ""),
+        };
+        rest = after;
+
+        if skipping.is_none() {
+            let content = decode_entities(content);
+
+            if in_title {
+                title.push_str(&content);
+            } else if in_pre {
+                text.push_str(&content);
+            } else {
+                let collapsed = content.split_whitespace().collect::<Vec<_>>().join(" ");
+                if !collapsed.is_empty() {
+                    let starts_line = text.is_empty() || text.ends_with('\n');
+                    if !starts_line && content.starts_with(char::is_whitespace) {
+                        text.push(' ');
+                    }
+                    text.push_str(&collapsed);
+                    if content.ends_with(char::is_whitespace) {
+                        text.push(' ');
+                    }
+                }
+            }
+        }
+
+        let Some(tag) = tag else {
+            continue;
+        };
+        let closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|letter: char| letter.is_whitespace() || letter == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        if let Some(skipped) = &skipping {
+            if closing && name == *skipped {
+                skipping = None;
+            }
+            continue;
+        }
+
+        match (name.as_str(), closing) {
+            ("script" | "style" | "head", false) => skipping = Some(name),
+            ("h1", false) if heading.is_none() => in_title = true,
+            ("h1", true) if in_title => {
+                in_title = false;
+                heading = Some(title.trim().to_string());
+            }
+            ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", false) => {
+                break_line(&mut text);
+                text.push_str(">>> ");
+            }
+            ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", true) => break_line(&mut text),
+            ("p" | "blockquote" | "ul" | "ol", true) => {
+                break_line(&mut text);
+                text.push('\n');
+            }
+            ("p" | "blockquote" | "div", _) => break_line(&mut text),
+            ("br", _) => {
+                trim_line(&mut text);
+                text.push('\n');
+            }
+            ("li", false) => {
+                break_line(&mut text);
+                text.push_str("- ");
+            }
+            ("hr", _) => {
+                break_line(&mut text);
+                text.push_str("* * *\n\n");
+            }
+            ("pre", false) => {
+                break_line(&mut text);
+                text.push_str("```\n");
+                in_pre = true;
+            }
+            ("pre", true) => {
+                in_pre = false;
+                break_line(&mut text);
+                text.push_str("```\n\n");
+            }
+            _ => {}
+        }
+    }
+
+    (heading, text)
+}
+
+fn trim_line(text: &mut String) {
+    while text.ends_with(' ') {
+        text.pop();
+    }
+}
+
+/// Ends the line being written, if one is.
+fn break_line(text: &mut String) {
+    trim_line(text);
+    if !text.is_empty() && !text.ends_with('\n') {
+        text.push('\n');
+    }
+}
+
+fn decode_entities(source: &str) -> String {
+    source
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&rsquo;", "’")
+        .replace("&lsquo;", "‘")
+        .replace("&rdquo;", "”")
+        .replace("&ldquo;", "“")
+        .replace("&hellip;", "…")
+        .replace("&mdash;", "—")
+        .replace("&ndash;", "–")
+        .replace("&larr;", "←")
+        .replace("&rarr;", "→")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markdown_paragraphs_become_single_lines() {
+        let (heading, text) = from_markdown("# Önsöz\n\nOne\nparagraph.\n\n## Part\nNext  \nline");
+
+        assert_eq!(heading.as_deref(), Some("Önsöz"));
+        assert_eq!(text, "One paragraph.\n\n>>> Part\nNext\nline");
+    }
+
+    #[test]
+    fn markdown_lists_and_code_keep_their_lines() {
+        let (_, text) = from_markdown("* one\n2. two\n\n```sh\nrpmbuild  -ba\n```");
+
+        assert_eq!(text, "- one\n2. two\n```sh\nrpmbuild  -ba\n```\n");
+    }
+
+    #[test]
+    fn links_are_spelled_out() {
+        assert_eq!(
+            inline_markdown("See [the book](https://doc.rust-lang.org/book/) [sic]"),
+            "See the book (https://doc.rust-lang.org/book/) [sic]"
+        );
+    }
+
+    #[test]
+    fn paired_emphasis_is_dropped() {
+        assert_eq!(
+            inline_markdown("**Bold**, *italic* and __both__ _ways_"),
+            "Bold, italic and both ways"
+        );
+    }
+
+    #[test]
+    fn lone_markers_and_code_are_left_alone() {
+        assert_eq!(inline_markdown("2 * 3 * 4 on *nix"), "2 * 3 * 4 on *nix");
+        assert_eq!(inline_markdown("my_var_name"), "my_var_name");
+        assert_eq!(
+            inline_markdown("Run `*p = **pp;` on _every_ `__init__`"),
+            "Run `*p = **pp;` on every `__init__`"
+        );
+    }
+
+    #[test]
+    fn html_headings_paragraphs_and_lists_are_kept() {
+        let (heading, text) = from_html(
+            "<head><title>x</title></head><h1>RPM &amp; Sen</h1>\
+             <p>Bir\n  paragraf.</p><h2>Liste</h2><ul><li>bir</li><li>iki</li></ul>",
+        );
+
+        assert_eq!(heading.as_deref(), Some("RPM & Sen"));
+        assert_eq!(text, "Bir paragraf.\n\n>>> Liste\n- bir\n- iki\n\n");
+    }
+
+    #[test]
+    fn html_preformatted_text_is_a_code_block() {
+        let (_, text) = from_html("<pre>%build\n  make</pre><script>alert(1)</script>");
+
+        assert_eq!(text, "```\n%build\n  make\n```\n\n");
+    }
+}
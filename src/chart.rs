@@ -0,0 +1,193 @@
+//! Charts drawn from inline data in page content.
+//!
+//! A fenced block tagged `bar`, `sparkline` or `gauge` holds the data as
+//! text. Bar charts pick `label=value` pairs out of whatever surrounds them,
+//! so a gdb style `$1 = {linux=1, rust=3}` can be charted as it is.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Direction, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Bar, BarChart, BarGroup, Gauge, Sparkline, Widget},
+};
+
+const ACCENT: Color = Color::LightMagenta;
+const SPARKLINE_HEIGHT: u16 = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Chart {
+    Bar(Vec<(String, u64)>),
+    Sparkline(Vec<u64>),
+    Gauge { label: String, ratio: f64 },
+}
+
+impl Chart {
+    /// Reads a chart of the given kind, if the kind is a chart at all and
+    /// the data makes sense for it.
+    pub fn parse(kind: &str, lines: &[&str]) -> Option<Chart> {
+        let text = lines.join("\n");
+        let tokens = || {
+            text.split(|letter: char| letter.is_whitespace() || ",{}()[]".contains(letter))
+                .filter(|token| !token.is_empty())
+        };
+
+        let chart = match kind {
+            "bar" => Chart::Bar(
+                tokens()
+                    .filter_map(|token| {
+                        let (label, value) = token.split_once(['=', ':'])?;
+                        Some((label.to_string(), value.parse().ok()?))
+                    })
+                    .collect(),
+            ),
+            "sparkline" => {
+                Chart::Sparkline(tokens().filter_map(|token| token.parse().ok()).collect())
+            }
+            "gauge" => {
+                // `NaN` parses as a number too, and would reach the gauge
+                // past the clamp.
+                let ratio = tokens().find_map(|token| {
+                    let ratio = match token.strip_suffix('%') {
+                        Some(percent) => percent.parse::<f64>().ok()? / 100.0,
+                        None => {
+                            let (value, total) = token.split_once('/')?;
+                            let total = total.parse::<f64>().ok().filter(|total| *total > 0.0)?;
+                            value.parse::<f64>().ok()? / total
+                        }
+                    };
+
+                    Some(ratio).filter(|ratio| ratio.is_finite())
+                })?;
+                let label = tokens()
+                    .filter(|token| !token.ends_with('%') && !token.contains('/'))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                Chart::Gauge {
+                    label,
+                    ratio: ratio.clamp(0.0, 1.0),
+                }
+            }
+            _ => return None,
+        };
+
+        match &chart {
+            Chart::Bar(bars) if bars.is_empty() => None,
+            Chart::Sparkline(values) if values.is_empty() => None,
+            _ => Some(chart),
+        }
+    }
+
+    pub fn height(&self) -> u16 {
+        match self {
+            Chart::Bar(bars) => bars.len().try_into().unwrap_or(u16::MAX),
+            Chart::Sparkline(_) => SPARKLINE_HEIGHT,
+            Chart::Gauge { .. } => 1,
+        }
+    }
+}
+
+impl Widget for &Chart {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        match self {
+            Chart::Bar(bars) => {
+                let bars: Vec<Bar> = bars
+                    .iter()
+                    .map(|(label, value)| Bar::default().label(label.as_str().into()).value(*value))
+                    .collect();
+
+                BarChart::default()
+                    .direction(Direction::Horizontal)
+                    .data(BarGroup::default().bars(&bars))
+                    .bar_width(1)
+                    .bar_gap(0)
+                    .bar_style(Style::new().fg(ACCENT))
+                    .value_style(Style::new().fg(Color::Black).bg(ACCENT))
+                    .label_style(Style::new().fg(Color::Gray))
+                    .render(area, buf);
+            }
+            Chart::Sparkline(values) => {
+                Sparkline::default()
+                    .data(values)
+                    .style(Style::new().fg(ACCENT))
+                    .render(area, buf);
+            }
+            Chart::Gauge { label, ratio } => {
+                Gauge::default()
+                    .ratio(*ratio)
+                    .label(format!("{label} {:.0}%", ratio * 100.0).trim().to_string())
+                    .gauge_style(Style::new().fg(ACCENT).bg(Color::Rgb(30, 30, 42)))
+                    .style(Style::new().add_modifier(Modifier::BOLD))
+                    .render(area, buf);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bars_are_picked_out_of_gdb_output() {
+        let chart = Chart::parse("bar", &["$1 = {linux=1, rust=3,", "  elixir:2}"]);
+
+        assert_eq!(
+            chart,
+            Some(Chart::Bar(vec![
+                ("linux".into(), 1),
+                ("rust".into(), 3),
+                ("elixir".into(), 2),
+            ]))
+        );
+    }
+
+    #[test]
+    fn sparklines_skip_what_is_not_a_number() {
+        let chart = Chart::parse("sparkline", &["[1, 5, x, 3]"]);
+
+        assert_eq!(chart, Some(Chart::Sparkline(vec![1, 5, 3])));
+    }
+
+    #[test]
+    fn gauges_take_a_percentage_or_a_fraction() {
+        assert_eq!(
+            Chart::parse("gauge", &["coffee 40%"]),
+            Some(Chart::Gauge {
+                label: "coffee".into(),
+                ratio: 0.4,
+            })
+        );
+        assert_eq!(
+            Chart::parse("gauge", &["sleep 3/12"]),
+            Some(Chart::Gauge {
+                label: "sleep".into(),
+                ratio: 0.25,
+            })
+        );
+    }
+
+    #[test]
+    fn gauges_stay_within_bounds() {
+        let chart = Chart::parse("gauge", &["150%"]);
+
+        assert_eq!(
+            chart,
+            Some(Chart::Gauge {
+                label: String::new(),
+                ratio: 1.0,
+            })
+        );
+    }
+
+    #[test]
+    fn blocks_without_data_are_not_charts() {
+        assert_eq!(Chart::parse("bar", &["nothing to see"]), None);
+        assert_eq!(Chart::parse("sparkline", &[""]), None);
+        assert_eq!(Chart::parse("gauge", &["3/0"]), None);
+        assert_eq!(Chart::parse("gauge", &["0/0"]), None);
+        assert_eq!(Chart::parse("gauge", &["x NaN%"]), None);
+        assert_eq!(Chart::parse("gauge", &["NaN/2 inf/1 -inf%"]), None);
+        assert_eq!(Chart::parse("rust", &["fn main() {}"]), None);
+    }
+}
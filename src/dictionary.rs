@@ -0,0 +1,838 @@
+//! The dictionary of the `./ducktongue` page, filed under
+//! `./portfolio/ducktongue` in the menu.
+//!
+//! A pocket DuckTongue: English words for Turkish speakers, bundled from
+//! `assets/dictionary`, found by a search box that forgives typos and
+//! also takes the Turkish side. With an empty search it shows the word of
+//! the day.
+//!
+//! Pointed at a DuckTongue server with `:endpoint <url>`, Enter asks
+//! `<url>/<word>` too. The server answers in DuckTongue's JSON:
+//!
+//! ```json
+//! {"word": "duck", "meanings": [{"part_of_speech": "noun",
+//!   "definition": "A water bird.", "translations": ["ördek"],
+//!   "example": "A duck was swimming in the pond."}]}
+//! ```
+//!
+//! The answer is read by a small JSON reader of its own, so that reading
+//! it needs no browser and can be tested.
+
+use std::{cell::RefCell, iter::Peekable, rc::Rc, str::Chars};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState, Paragraph, StatefulWidget, Widget, Wrap},
+};
+use ratzilla::event::{KeyCode, KeyEvent};
+use web_sys::{
+    wasm_bindgen::{closure::Closure, JsCast},
+    XmlHttpRequest,
+};
+
+use crate::{focus::Reply, random, storage};
+
+const DATASET: &str = include_str!("../assets/dictionary/en-tr.tsv");
+const CURSOR: char = '█';
+const LIST_WIDTH: u16 = 24;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Word {
+    pub word: String,
+    pub part_of_speech: String,
+    pub definition: String,
+    pub translations: Vec<String>,
+    pub example: String,
+}
+
+impl Word {
+    fn parse(line: &str) -> Option<Word> {
+        let mut fields = line.split('\t');
+
+        Some(Word {
+            word: fields.next()?.to_string(),
+            part_of_speech: fields.next()?.to_string(),
+            definition: fields.next()?.to_string(),
+            translations: fields
+                .next()?
+                .split(',')
+                .map(|translation| translation.trim().to_string())
+                .collect(),
+            example: fields.next().unwrap_or_default().to_string(),
+        })
+    }
+
+    /// Reads a meaning out of the JSON of a DuckTongue server.
+    fn from_json(word: &str, meaning: &Json) -> Word {
+        let string = |name: &str| meaning.get(name).and_then(Json::as_str).unwrap_or_default();
+
+        Word {
+            word: word.to_string(),
+            part_of_speech: string("part_of_speech").to_string(),
+            definition: string("definition").to_string(),
+            translations: meaning
+                .get("translations")
+                .map(Json::items)
+                .unwrap_or_default()
+                .iter()
+                .filter_map(Json::as_str)
+                .map(str::to_string)
+                .collect(),
+            example: string("example").to_string(),
+        }
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let mut lines = vec![
+            Line::from(vec![
+                Span::from(self.word.clone()).bold().light_magenta(),
+                Span::from(format!("  {}", self.part_of_speech)).dark_gray(),
+            ]),
+            Line::from(self.definition.clone()),
+            Line::default(),
+            Line::from(vec![
+                Span::from("tr  ").dark_gray(),
+                Span::from(self.translations.join(", ")).light_cyan(),
+            ]),
+        ];
+
+        if !self.example.is_empty() {
+            lines.push(
+                Line::from(format!("\"{}\"", self.example))
+                    .dark_gray()
+                    .italic(),
+            );
+        }
+        lines
+    }
+}
+
+/// Optimal string alignment distance: edits, with a swap of neighbours
+/// counting as one.
+fn distance(left: &[char], right: &[char]) -> usize {
+    let mut rows = vec![vec![0; right.len() + 1]; left.len() + 1];
+
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=left.len() {
+        for j in 1..=right.len() {
+            let cost = usize::from(left[i - 1] != right[j - 1]);
+
+            rows[i][j] = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && left[i - 1] == right[j - 2] && left[i - 2] == right[j - 1] {
+                rows[i][j] = rows[i][j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    rows[left.len()][right.len()]
+}
+
+/// How well `query` matches `candidate`, both lowercase, if at all:
+/// exact words first, then prefixes, substrings, scattered letters and
+/// finally near misses.
+fn similarity(query: &str, candidate: &str) -> Option<u32> {
+    let length = |text: &str| text.chars().count() as u32;
+    let difference = length(candidate).abs_diff(length(query));
+
+    if candidate == query {
+        return Some(1000);
+    }
+    if candidate.starts_with(query) {
+        return Some(900u32.saturating_sub(difference));
+    }
+    if candidate.contains(query) {
+        return Some(700u32.saturating_sub(difference));
+    }
+
+    let mut letters = candidate.chars();
+    if query
+        .chars()
+        .all(|letter| letters.any(|other| other == letter))
+    {
+        return Some(500u32.saturating_sub(difference * 10));
+    }
+
+    let query: Vec<char> = query.chars().collect();
+    let candidate: Vec<char> = candidate.chars().collect();
+    let edits = distance(&query, &candidate);
+
+    (edits <= (query.len() / 3).max(1)).then(|| 300u32.saturating_sub(edits as u32 * 100))
+}
+
+/// Turkish matches rank a little under English ones.
+fn score(query: &str, word: &Word) -> Option<u32> {
+    let english = similarity(query, &word.word.to_lowercase());
+    let turkish = word
+        .translations
+        .iter()
+        .filter_map(|translation| similarity(query, &translation.to_lowercase()))
+        .max()
+        .map(|score| score.saturating_sub(50));
+
+    english.max(turkish)
+}
+
+/// The indices of the words matching `query`, best first, ties in the
+/// order of `words`.
+fn rank(query: &str, words: &[Word]) -> Vec<usize> {
+    let mut scored: Vec<(u32, usize)> = words
+        .iter()
+        .enumerate()
+        .filter_map(|(index, word)| Some((score(query, word)?, index)))
+        .collect();
+    scored.sort_by(|left, right| right.0.cmp(&left.0).then(left.1.cmp(&right.1)));
+
+    scored.into_iter().map(|(_, index)| index).collect()
+}
+
+/// Just enough JSON for the answers of a DuckTongue server.
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// Reads a whole document, or nothing if any of it is not JSON.
+    fn parse(text: &str) -> Option<Json> {
+        let mut chars = text.chars().peekable();
+        let json = Json::value(&mut chars)?;
+
+        Json::skip_whitespace(&mut chars);
+        chars.peek().is_none().then_some(json)
+    }
+
+    fn skip_whitespace(chars: &mut Peekable<Chars>) {
+        while chars.next_if(|letter| letter.is_whitespace()).is_some() {}
+    }
+
+    fn value(chars: &mut Peekable<Chars>) -> Option<Json> {
+        Json::skip_whitespace(chars);
+
+        match *chars.peek()? {
+            '"' => Json::string(chars).map(Json::String),
+            '[' => {
+                chars.next();
+                let mut items = Vec::new();
+
+                Json::skip_whitespace(chars);
+                if chars.next_if_eq(&']').is_some() {
+                    return Some(Json::Array(items));
+                }
+                loop {
+                    items.push(Json::value(chars)?);
+                    Json::skip_whitespace(chars);
+                    match chars.next()? {
+                        ',' => {}
+                        ']' => return Some(Json::Array(items)),
+                        _ => return None,
+                    }
+                }
+            }
+            '{' => {
+                chars.next();
+                let mut fields = Vec::new();
+
+                Json::skip_whitespace(chars);
+                if chars.next_if_eq(&'}').is_some() {
+                    return Some(Json::Object(fields));
+                }
+                loop {
+                    Json::skip_whitespace(chars);
+                    let name = Json::string(chars)?;
+                    Json::skip_whitespace(chars);
+                    chars.next_if_eq(&':')?;
+                    fields.push((name, Json::value(chars)?));
+                    Json::skip_whitespace(chars);
+                    match chars.next()? {
+                        ',' => {}
+                        '}' => return Some(Json::Object(fields)),
+                        _ => return None,
+                    }
+                }
+            }
+            _ => {
+                let mut word = String::new();
+                while let Some(letter) = chars
+                    .next_if(|letter| letter.is_ascii_alphanumeric() || "+-.".contains(*letter))
+                {
+                    word.push(letter);
+                }
+
+                match word.as_str() {
+                    "null" => Some(Json::Null),
+                    "true" => Some(Json::Bool(true)),
+                    "false" => Some(Json::Bool(false)),
+                    number => number.parse().ok().map(Json::Number),
+                }
+            }
+        }
+    }
+
+    fn string(chars: &mut Peekable<Chars>) -> Option<String> {
+        chars.next_if_eq(&'"')?;
+        let mut string = String::new();
+
+        loop {
+            match chars.next()? {
+                '"' => return Some(string),
+                '\\' => string.push(match chars.next()? {
+                    'n' => '\n',
+                    't' => '\t',
+                    'r' => '\r',
+                    'b' => '\u{8}',
+                    'f' => '\u{c}',
+                    'u' => {
+                        let code: String = chars.by_ref().take(4).collect();
+                        let code = u32::from_str_radix(&code, 16).ok()?;
+                        char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+                    }
+                    escaped => escaped,
+                }),
+                letter => string.push(letter),
+            }
+        }
+    }
+
+    fn get(&self, name: &str) -> Option<&Json> {
+        match self {
+            Json::Object(fields) => fields
+                .iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// The items of an array, and none of anything else.
+    fn items(&self) -> &[Json] {
+        match self {
+            Json::Array(items) => items,
+            _ => &[],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+enum Remote {
+    #[default]
+    Idle,
+    Asking(String),
+    Found(Vec<Word>),
+    Missing(String),
+    Failed(String),
+}
+
+impl Remote {
+    /// What a server answering `status` with `body` says about `asked`.
+    fn answer(asked: &str, status: u16, body: Option<&str>) -> Remote {
+        match status {
+            200 => {
+                let Some(json) = body.and_then(Json::parse) else {
+                    return Remote::Failed("The answer was not JSON.".into());
+                };
+                let word = json.get("word").and_then(Json::as_str).unwrap_or(asked);
+                let meanings = json.get("meanings").map(Json::items).unwrap_or_default();
+
+                if meanings.is_empty() {
+                    Remote::Missing(word.to_string())
+                } else {
+                    Remote::Found(
+                        meanings
+                            .iter()
+                            .map(|meaning| Word::from_json(word, meaning))
+                            .collect(),
+                    )
+                }
+            }
+            404 => Remote::Missing(asked.to_string()),
+            0 => Remote::Failed("The endpoint could not be reached.".into()),
+            status => Remote::Failed(format!("The endpoint answered {status}.")),
+        }
+    }
+
+    /// Takes in the answer about `asked`, unless a newer search has moved
+    /// on from it.
+    fn settle(&mut self, asked: &str, status: u16, body: Option<&str>) {
+        if matches!(self, Remote::Asking(word) if word == asked) {
+            *self = Remote::answer(asked, status, body);
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Dictionary {
+    words: Vec<Word>,
+    query: String,
+    matches: Vec<usize>,
+    state: ListState,
+    endpoint: Option<String>,
+    /// Shared with the request in flight, which fills it in when the
+    /// server answers.
+    remote: Rc<RefCell<Remote>>,
+    /// The request in flight and the callback it answers to, kept alive
+    /// until the next request replaces them.
+    request: Option<(XmlHttpRequest, Closure<dyn FnMut()>)>,
+    notice: Option<String>,
+}
+
+impl Default for Dictionary {
+    fn default() -> Self {
+        let mut words: Vec<Word> = DATASET
+            .lines()
+            .filter(|line| !line.starts_with('#') && !line.trim().is_empty())
+            .filter_map(Word::parse)
+            .collect();
+        words.sort_by(|left, right| left.word.cmp(&right.word));
+
+        let mut dictionary = Self {
+            words,
+            query: String::new(),
+            matches: Vec::new(),
+            state: ListState::default(),
+            endpoint: storage::get("ducktongue_endpoint"),
+            remote: Rc::default(),
+            request: None,
+            notice: None,
+        };
+
+        dictionary.search();
+        dictionary
+    }
+}
+
+impl Dictionary {
+    /// Picks the same word all day, a new one every day.
+    fn word_of_the_day(&self) -> usize {
+        let days = web_time::SystemTime::now()
+            .duration_since(web_time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs() / 86_400);
+
+        (random::noise(days) % self.words.len().max(1) as u64) as usize
+    }
+
+    fn search(&mut self) {
+        let query = self.query.trim().to_lowercase();
+        *self.remote.borrow_mut() = Remote::Idle;
+
+        if query.is_empty() {
+            self.matches = (0..self.words.len()).collect();
+            self.state.select(Some(self.word_of_the_day()));
+            return;
+        }
+
+        self.matches = rank(&query, &self.words);
+        self.state.select(Some(0));
+    }
+
+    pub fn handle(&mut self, event: &KeyEvent) -> Reply {
+        if event.ctrl || event.alt {
+            return Reply::Ignored;
+        }
+
+        match event.code {
+            KeyCode::Char(letter) => {
+                self.query.push(letter);
+                self.notice = None;
+                self.search();
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.search();
+            }
+            KeyCode::Up => self.state.select_previous(),
+            KeyCode::Down => {
+                let last = self.matches.len().saturating_sub(1);
+                self.state.select(Some(
+                    self.state
+                        .selected()
+                        .map_or(0, |index| (index + 1).min(last)),
+                ));
+            }
+            KeyCode::Enter => {
+                let query = self.query.trim().to_string();
+
+                match query.strip_prefix(':') {
+                    Some(command) => self.command(command),
+                    None => self.ask(),
+                }
+                return Reply::Ran;
+            }
+            _ => return Reply::Ignored,
+        }
+
+        Reply::Consumed
+    }
+
+    fn command(&mut self, command: &str) {
+        let mut words = command.split_whitespace();
+
+        self.notice = Some(match (words.next(), words.next()) {
+            (Some("endpoint"), Some("off")) => {
+                self.endpoint = None;
+                storage::set("ducktongue_endpoint", "");
+                "Only the bundled dictionary now.".into()
+            }
+            (Some("endpoint"), Some(url)) => {
+                let url = url.trim_end_matches('/').to_string();
+                storage::set("ducktongue_endpoint", &url);
+                self.endpoint = Some(url.clone());
+                format!("Enter asks {url} too.")
+            }
+            (Some("endpoint"), None) => match &self.endpoint {
+                Some(url) => format!("Asking {url}. :endpoint off to stop."),
+                None => "No endpoint. :endpoint <url> to set one.".into(),
+            },
+            _ => format!("Unknown command :{command}. Try :endpoint <url>."),
+        });
+
+        self.query.clear();
+        self.search();
+    }
+
+    /// Asks the DuckTongue server about the query, if there is one.
+    fn ask(&mut self) {
+        let word = self.query.trim().to_lowercase();
+        let Some(endpoint) = self.endpoint.clone().filter(|url| !url.is_empty()) else {
+            return;
+        };
+        if word.is_empty() {
+            return;
+        }
+
+        let Ok(request) = XmlHttpRequest::new() else {
+            *self.remote.borrow_mut() = Remote::Failed("This browser can't send requests.".into());
+            return;
+        };
+
+        let url = format!(
+            "{endpoint}/{}",
+            web_sys::js_sys::encode_uri_component(&word)
+        );
+        if request.open("GET", &url).is_err() {
+            *self.remote.borrow_mut() = Remote::Failed(format!("Can't ask {url}."));
+            return;
+        }
+
+        let remote = self.remote.clone();
+        let answered = request.clone();
+        let asked = word.clone();
+        let on_load_end = Closure::<dyn FnMut()>::new(move || {
+            let body = answered.response_text().ok().flatten();
+            remote.borrow_mut().settle(
+                &asked,
+                answered.status().unwrap_or_default(),
+                body.as_deref(),
+            );
+        });
+        request.set_onloadend(Some(on_load_end.as_ref().unchecked_ref()));
+
+        // The callback of the last request is dropped with it, so it must
+        // not fire any more.
+        if let Some((previous, _)) = self.request.take() {
+            previous.set_onloadend(None);
+            let _ = previous.abort();
+        }
+
+        *self.remote.borrow_mut() = match request.send() {
+            Ok(()) => Remote::Asking(word),
+            Err(_) => Remote::Failed(format!("Can't ask {url}.")),
+        };
+        self.request = Some((request, on_load_end));
+    }
+
+    fn status(&self) -> Option<Line<'static>> {
+        if let Some(notice) = &self.notice {
+            return Some(Line::from(notice.clone()).dark_gray());
+        }
+
+        let endpoint = self.endpoint.as_ref().filter(|url| !url.is_empty())?;
+        let status = match &*self.remote.borrow() {
+            Remote::Idle => format!("Enter asks {endpoint} too."),
+            Remote::Asking(word) => format!("Asking {endpoint} about {word}…"),
+            Remote::Found(meanings) => format!("{endpoint} knows {} meanings.", meanings.len()),
+            Remote::Missing(word) => format!("{endpoint} doesn't know {word} either."),
+            Remote::Failed(error) => return Some(Line::from(error.clone()).light_red()),
+        };
+        Some(Line::from(status).dark_gray())
+    }
+
+    fn detail(&self) -> Vec<Line<'static>> {
+        if let Remote::Found(meanings) = &*self.remote.borrow() {
+            return meanings
+                .iter()
+                .flat_map(|meaning| {
+                    let mut lines = meaning.lines();
+                    lines.push(Line::default());
+                    lines
+                })
+                .collect();
+        }
+
+        let selected = self.state.selected().unwrap_or_default();
+        let Some(word) = self.matches.get(selected).map(|index| &self.words[*index]) else {
+            return vec![Line::from("No such word, not even close.").dark_gray()];
+        };
+
+        let mut lines = Vec::new();
+        if self.query.trim().is_empty() && self.matches[selected] == self.word_of_the_day() {
+            lines.push(Line::from("Word of the day").light_yellow());
+            lines.push(Line::default());
+        }
+
+        lines.extend(word.lines());
+        lines
+    }
+}
+
+impl Widget for &Dictionary {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let status = self.status();
+        let [search_area, body_area, status_area] = Layout::vertical([
+            Constraint::Length(2),
+            Constraint::Fill(1),
+            Constraint::Length(u16::from(status.is_some())),
+        ])
+        .areas(area);
+
+        Line::from(vec![
+            Span::from("search: ").light_magenta(),
+            Span::from(self.query.clone()).light_cyan(),
+            Span::from(CURSOR.to_string()).light_cyan(),
+        ])
+        .render(search_area, buf);
+
+        let [list_area, detail_area] =
+            Layout::horizontal([Constraint::Length(LIST_WIDTH), Constraint::Fill(1)])
+                .spacing(2)
+                .areas(body_area);
+
+        let items: Vec<ListItem> = self
+            .matches
+            .iter()
+            .map(|index| {
+                let word = &self.words[*index];
+                ListItem::new(Line::from(vec![
+                    Span::from(word.word.clone()),
+                    Span::from(format!(" {}", word.part_of_speech)).dark_gray(),
+                ]))
+            })
+            .collect();
+
+        let mut state = self.state.clone();
+        StatefulWidget::render(
+            List::new(items)
+                .highlight_style(Style::default().fg(Color::LightMagenta))
+                .highlight_symbol("▶ "),
+            list_area,
+            buf,
+            &mut state,
+        );
+
+        Paragraph::new(self.detail())
+            .wrap(Wrap { trim: false })
+            .render(detail_area, buf);
+
+        if let Some(status) = status {
+            status.render(status_area, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUCK: &str = r#"{
+        "word": "duck",
+        "meanings": [
+            {
+                "part_of_speech": "noun",
+                "definition": "A water bird.",
+                "translations": ["ördek", "baş eğmek"],
+                "example": "A \"duck\" was swimming in the pond."
+            },
+            {"part_of_speech": "verb", "definition": "To lower the head.", "translations": []}
+        ],
+        "source": null,
+        "frequency": 4.5e1,
+        "common": true
+    }"#;
+
+    #[test]
+    fn every_meaning_is_a_word() {
+        let Remote::Found(meanings) = Remote::answer("DUCK", 200, Some(DUCK)) else {
+            panic!("expected meanings");
+        };
+
+        assert_eq!(
+            meanings[0],
+            Word {
+                word: "duck".into(),
+                part_of_speech: "noun".into(),
+                definition: "A water bird.".into(),
+                translations: vec!["ördek".into(), "baş eğmek".into()],
+                example: "A \"duck\" was swimming in the pond.".into(),
+            }
+        );
+        assert_eq!(meanings[1].part_of_speech, "verb");
+        assert!(meanings[1].translations.is_empty());
+        assert!(meanings[1].example.is_empty());
+    }
+
+    #[test]
+    fn no_meanings_is_a_missing_word() {
+        assert_eq!(
+            Remote::answer("duck", 200, Some(r#"{"meanings": []}"#)),
+            Remote::Missing("duck".into())
+        );
+        assert_eq!(
+            Remote::answer("duck", 404, Some("Not Found")),
+            Remote::Missing("duck".into())
+        );
+    }
+
+    #[test]
+    fn unreachable_or_garbled_answers_fail() {
+        assert!(matches!(Remote::answer("duck", 0, None), Remote::Failed(_)));
+        assert!(matches!(
+            Remote::answer("duck", 200, Some("<html>")),
+            Remote::Failed(_)
+        ));
+        assert!(matches!(
+            Remote::answer("duck", 200, Some(r#"{"word": "duck""#)),
+            Remote::Failed(_)
+        ));
+        assert_eq!(
+            Remote::answer("duck", 500, None),
+            Remote::Failed("The endpoint answered 500.".into())
+        );
+    }
+
+    #[test]
+    fn json_is_read_whole_or_not_at_all() {
+        assert_eq!(
+            Json::parse(r#" [1, -2.5, "a\\b", {}, []] "#),
+            Some(Json::Array(vec![
+                Json::Number(1.0),
+                Json::Number(-2.5),
+                Json::String("a\\b".into()),
+                Json::Object(Vec::new()),
+                Json::Array(Vec::new()),
+            ]))
+        );
+        assert_eq!(Json::parse("[1, 2] 3"), None);
+        assert_eq!(Json::parse("[1, 2,]"), None);
+        assert_eq!(Json::parse(""), None);
+    }
+
+    fn word(word: &str, translations: &[&str]) -> Word {
+        Word {
+            word: word.into(),
+            part_of_speech: "noun".into(),
+            definition: String::new(),
+            translations: translations
+                .iter()
+                .map(|translation| translation.to_string())
+                .collect(),
+            example: String::new(),
+        }
+    }
+
+    #[test]
+    fn swapped_letters_are_one_edit() {
+        let chars = |text: &str| text.chars().collect::<Vec<_>>();
+
+        assert_eq!(distance(&chars("duck"), &chars("duck")), 0);
+        assert_eq!(distance(&chars("dukc"), &chars("duck")), 1);
+        assert_eq!(distance(&chars("dock"), &chars("duck")), 1);
+        assert_eq!(distance(&chars("ördek"), &chars("ordek")), 1);
+        assert_eq!(distance(&chars(""), &chars("duck")), 4);
+    }
+
+    #[test]
+    fn closer_matches_are_more_similar() {
+        let ranked = [
+            similarity("duck", "duck"),
+            similarity("duck", "duckling"),
+            similarity("duck", "ducks"),
+            similarity("uck", "duck"),
+            similarity("dk", "duck"),
+            similarity("dukc", "duck"),
+        ];
+
+        assert_eq!(
+            ranked,
+            [
+                Some(1000),
+                Some(896),
+                Some(899),
+                Some(699),
+                Some(480),
+                Some(200)
+            ]
+        );
+        assert_eq!(similarity("goose", "duck"), None);
+    }
+
+    #[test]
+    fn translations_rank_under_words() {
+        let duck = word("duck", &["ördek"]);
+
+        assert_eq!(score("duck", &duck), Some(1000));
+        assert_eq!(score("ördek", &duck), Some(950));
+        assert_eq!(score("goose", &duck), None);
+    }
+
+    #[test]
+    fn searches_rank_best_first() {
+        let words = [
+            word("bird", &["kuş"]),
+            word("duckling", &["ördek yavrusu"]),
+            word("duck", &["ördek"]),
+            word("pond", &["gölet"]),
+            word("dock", &["rıhtım"]),
+        ];
+
+        assert_eq!(rank("duck", &words), [2, 1, 4]);
+        assert_eq!(rank("ördek", &words), [2, 1]);
+        assert_eq!(rank("kuş", &words), [0]);
+        assert!(rank("zebra", &words).is_empty());
+    }
+
+    #[test]
+    fn answers_only_settle_the_word_still_asked() {
+        let mut remote = Remote::Asking("duck".into());
+
+        remote.settle("goose", 404, None);
+        assert_eq!(remote, Remote::Asking("duck".into()));
+
+        remote.settle("duck", 200, Some(DUCK));
+        assert!(matches!(remote, Remote::Found(ref meanings) if meanings.len() == 2));
+
+        remote.settle("duck", 404, None);
+        assert!(matches!(remote, Remote::Found(_)));
+    }
+}
@@ -0,0 +1,515 @@
+//! Page content.
+//!
+//! Pages are plain text with a little markup on top: `>>>` lines are
+//! headings, links are highlighted, fenced code blocks are syntax
+//! highlighted, fenced `bar`, `sparkline` and `gauge` blocks are charts
+//! and runs of `| pipe | separated |` lines are tables. A document is
+//! laid out against the width of the pane and drawn one section at a time
+//! into offscreen buffers, so the pane can scroll through prose and code
+//! alike.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Cell, Paragraph, Row, Table, Widget, Wrap},
+};
+
+use crate::{
+    chart::Chart,
+    gradient::{self, Direction, Gradient},
+    highlight,
+};
+
+const FENCE: &str = "```";
+const CODE_BACKGROUND: Color = Color::Rgb(30, 30, 42);
+const BORDER: Color = Color::LightMagenta;
+/// Columns are never squeezed narrower than this, ellipsis included.
+const MIN_COLUMN_WIDTH: usize = 3;
+
+#[derive(Debug)]
+enum Section<'a> {
+    Prose(Vec<&'a str>),
+    Code {
+        language: &'a str,
+        lines: Vec<&'a str>,
+    },
+    /// With `raw`, the data is shown as written above the chart.
+    Chart {
+        chart: Chart,
+        source: Vec<&'a str>,
+        raw: bool,
+    },
+    Table {
+        header: Option<Vec<&'a str>>,
+        alignments: Vec<Alignment>,
+        rows: Vec<Vec<&'a str>>,
+    },
+}
+
+impl Section<'_> {
+    fn height(&self, width: u16) -> u16 {
+        let height = match self {
+            Section::Prose(lines) => prose(lines).line_count(width),
+            // A row for the language tag and an empty one below the code.
+            Section::Code { lines, .. } => lines.len() + 2,
+            Section::Chart { chart, source, raw } => {
+                let caption = if *raw {
+                    raw_source(source).line_count(width)
+                } else {
+                    0
+                };
+                caption + usize::from(chart.height())
+            }
+            // Borders, and the header with the gap below it.
+            Section::Table { header, rows, .. } => {
+                rows.len() + 2 + if header.is_some() { 2 } else { 0 }
+            }
+        };
+
+        height.try_into().unwrap_or(u16::MAX)
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer, horizontal_scroll: u16) {
+        match self {
+            Section::Prose(lines) => prose(lines).render(area, buf),
+            Section::Code { language, lines } => {
+                code(language, lines, area, buf, horizontal_scroll)
+            }
+            Section::Chart { chart, source, raw } => {
+                let caption = if *raw {
+                    raw_source(source).line_count(area.width) as u16
+                } else {
+                    0
+                };
+                let [caption_area, chart_area] =
+                    Layout::vertical([Constraint::Length(caption), Constraint::Fill(1)])
+                        .areas(area);
+
+                raw_source(source).render(caption_area, buf);
+                chart.render(chart_area, buf);
+            }
+            Section::Table {
+                header,
+                alignments,
+                rows,
+            } => table(header.as_deref(), alignments, rows, area, buf),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Document<'a> {
+    sections: Vec<Section<'a>>,
+}
+
+impl<'a> Document<'a> {
+    pub fn parse(text: &'a str) -> Self {
+        let mut sections = Vec::new();
+        let mut prose = Vec::new();
+        let mut lines = text.split('\n').peekable();
+
+        while let Some(line) = lines.next() {
+            if is_table_row(line) {
+                if !prose.is_empty() {
+                    sections.push(Section::Prose(std::mem::take(&mut prose)));
+                }
+
+                let mut table = vec![line];
+                while let Some(row) = lines.next_if(|line| is_table_row(line)) {
+                    table.push(row);
+                }
+                sections.push(parse_table(&table));
+                continue;
+            }
+
+            let Some(language) = line.trim_start().strip_prefix(FENCE) else {
+                prose.push(line);
+                continue;
+            };
+
+            if !prose.is_empty() {
+                sections.push(Section::Prose(std::mem::take(&mut prose)));
+            }
+
+            let code: Vec<&str> = lines
+                .by_ref()
+                .take_while(|line| line.trim() != FENCE)
+                .collect();
+            let mut tag = language.split_whitespace();
+            let kind = tag.next().unwrap_or_default();
+
+            sections.push(match Chart::parse(kind, &code) {
+                Some(chart) => Section::Chart {
+                    chart,
+                    source: code,
+                    raw: tag.any(|option| option == "raw"),
+                },
+                None => Section::Code {
+                    language: language.trim(),
+                    lines: code,
+                },
+            });
+        }
+
+        if !prose.is_empty() {
+            sections.push(Section::Prose(prose));
+        }
+
+        Self { sections }
+    }
+
+    /// The number of rows the document takes at the given width.
+    pub fn height(&self, width: u16) -> u16 {
+        self.sections.iter().fold(0, |height, section| {
+            height.saturating_add(section.height(width))
+        })
+    }
+
+    /// How far code blocks can scroll sideways at the given width, which is
+    /// as far as it takes to reveal the end of their longest line.
+    pub fn overflow(&self, width: u16) -> u16 {
+        self.sections
+            .iter()
+            .filter_map(|section| match section {
+                Section::Code { lines, .. } => {
+                    let longest = lines.iter().map(|line| line.chars().count()).max()?;
+                    let visible = usize::from(width).saturating_sub(gutter_width(lines.len()));
+                    Some(longest.saturating_sub(visible))
+                }
+                Section::Prose(_) | Section::Chart { .. } | Section::Table { .. } => None,
+            })
+            .max()
+            .unwrap_or(0)
+            .try_into()
+            .unwrap_or(u16::MAX)
+    }
+
+    /// Draws the rows from `scroll` on into `area`, keeping whatever style
+    /// the area already has as the background.
+    pub fn render(&self, area: Rect, buf: &mut Buffer, scroll: u16, horizontal_scroll: u16) {
+        if area.is_empty() {
+            return;
+        }
+
+        let background = buf[(area.x, area.y)].clone();
+        let bottom = scroll.saturating_add(area.height);
+        let mut top = 0u16;
+
+        for section in &self.sections {
+            let height = section.height(area.width);
+            let section_bottom = top.saturating_add(height);
+
+            if section_bottom > scroll && top < bottom {
+                let section_area = Rect::new(0, 0, area.width, height);
+                let mut offscreen = Buffer::filled(section_area, background.clone());
+                section.render(section_area, &mut offscreen, horizontal_scroll);
+
+                for row in top.max(scroll)..section_bottom.min(bottom) {
+                    for column in 0..area.width {
+                        buf[(area.x + column, area.y + row - scroll)] =
+                            offscreen[(column, row - top)].clone();
+                    }
+                }
+            }
+
+            top = section_bottom;
+        }
+    }
+}
+
+fn is_table_row(line: &str) -> bool {
+    line.trim_start().starts_with('|')
+}
+
+fn cells(row: &str) -> Vec<&str> {
+    let row = row.trim();
+    let row = row.strip_prefix('|').unwrap_or(row);
+    let row = row.strip_suffix('|').unwrap_or(row);
+
+    row.split('|').map(str::trim).collect()
+}
+
+/// A separator row such as `|:--|--:|:-:|`, giving each column's
+/// alignment by where its colons are.
+fn alignments(row: &str) -> Option<Vec<Alignment>> {
+    cells(row)
+        .into_iter()
+        .map(|cell| {
+            let dashes = cell.trim_start_matches(':').trim_end_matches(':');
+            if dashes.is_empty() || !dashes.chars().all(|letter| letter == '-') {
+                return None;
+            }
+
+            Some(match (cell.starts_with(':'), cell.ends_with(':')) {
+                (true, true) => Alignment::Center,
+                (false, true) => Alignment::Right,
+                _ => Alignment::Left,
+            })
+        })
+        .collect()
+}
+
+/// The first row is a header when a separator row follows it.
+fn parse_table<'a>(lines: &[&'a str]) -> Section<'a> {
+    match lines {
+        [header, separator, rows @ ..] if alignments(separator).is_some() => Section::Table {
+            header: Some(cells(header)),
+            alignments: alignments(separator).unwrap_or_default(),
+            rows: rows.iter().map(|row| cells(row)).collect(),
+        },
+        rows => Section::Table {
+            header: None,
+            alignments: Vec::new(),
+            rows: rows.iter().map(|row| cells(row)).collect(),
+        },
+    }
+}
+
+/// Natural column widths, squeezed widest first until the table fits.
+fn column_widths(header: Option<&[&str]>, rows: &[Vec<&str>], available: usize) -> Vec<usize> {
+    let all_rows = || rows.iter().map(Vec::as_slice).chain(header);
+    let columns = all_rows().map(<[&str]>::len).max().unwrap_or(0);
+    let mut widths = vec![0; columns];
+
+    for row in all_rows() {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let spacing = columns.saturating_sub(1);
+    while widths.iter().sum::<usize>() + spacing > available {
+        let Some(widest) = widths
+            .iter_mut()
+            .filter(|width| **width > MIN_COLUMN_WIDTH)
+            .max_by_key(|width| **width)
+        else {
+            break;
+        };
+        *widest -= 1;
+    }
+
+    widths
+}
+
+/// Cuts `text` down to `width` characters, marking the cut with an ellipsis.
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+
+    let mut truncated: String = text.chars().take(width.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+fn table(
+    header: Option<&[&str]>,
+    alignments: &[Alignment],
+    rows: &[Vec<&str>],
+    area: Rect,
+    buf: &mut Buffer,
+) {
+    let block = Block::bordered().border_style(Style::new().fg(BORDER));
+    let available = usize::from(block.inner(area).width);
+    let widths = column_widths(header, rows, available);
+
+    let row = |cells: &[&str]| {
+        Row::new(widths.iter().enumerate().map(|(column, width)| {
+            let text = truncate(cells.get(column).copied().unwrap_or_default(), *width);
+            let alignment = alignments.get(column).copied().unwrap_or_default();
+
+            Cell::from(Line::from(text).alignment(alignment))
+        }))
+    };
+
+    let mut table = Table::new(
+        rows.iter().map(|cells| row(cells)),
+        widths.iter().map(|width| Constraint::Length(*width as u16)),
+    )
+    .block(block)
+    .column_spacing(1);
+
+    if let Some(header) = header {
+        table = table.header(
+            row(header)
+                .style(Style::new().fg(BORDER).add_modifier(Modifier::BOLD))
+                .bottom_margin(1),
+        );
+    }
+
+    Widget::render(table, area, buf);
+}
+
+fn raw_source<'a>(lines: &[&'a str]) -> Paragraph<'a> {
+    Paragraph::new(
+        lines
+            .iter()
+            .map(|line| Line::from(*line))
+            .collect::<Vec<_>>(),
+    )
+    .wrap(Wrap { trim: true })
+}
+
+fn prose<'a>(lines: &[&'a str]) -> Paragraph<'a> {
+    let lines: Vec<Line> = lines
+        .iter()
+        .map(|line| {
+            if line.starts_with(">>>") {
+                return Gradient::new(gradient::NEON, Direction::Horizontal)
+                    .paint_line(Line::from(line.add_modifier(Modifier::BOLD)));
+            }
+
+            Line::from(if line.starts_with("http") {
+                line.light_blue().style(Modifier::SLOW_BLINK)
+            } else {
+                Span::from(*line)
+            })
+        })
+        .collect();
+
+    Paragraph::new(lines).wrap(Wrap { trim: true })
+}
+
+/// Line numbers, right aligned, and a space.
+fn gutter_width(lines: usize) -> usize {
+    lines.max(1).ilog10() as usize + 2
+}
+
+fn code(language: &str, lines: &[&str], area: Rect, buf: &mut Buffer, horizontal_scroll: u16) {
+    buf.set_style(area, Style::new().bg(CODE_BACKGROUND));
+
+    let tag = format!(
+        " {} ",
+        highlight::grammar(language).map_or(language, |grammar| grammar.name)
+    );
+    let tag_width = (tag.chars().count() as u16).min(area.width);
+    buf.set_string(
+        area.right() - tag_width,
+        area.y,
+        tag,
+        Style::new().fg(Color::DarkGray),
+    );
+
+    let gutter = gutter_width(lines.len());
+    let code_width = usize::from(area.width).saturating_sub(gutter);
+    let highlighted = highlight::highlight(highlight::grammar(language), lines);
+
+    for (index, line) in highlighted.into_iter().enumerate() {
+        let y = area.y + 1 + index as u16;
+
+        buf.set_string(
+            area.x,
+            y,
+            format!("{:>width$} ", index + 1, width = gutter - 1),
+            Style::new().fg(Color::DarkGray),
+        );
+
+        let visible = skip_columns(line, usize::from(horizontal_scroll));
+        buf.set_line(area.x + gutter as u16, y, &visible, code_width as u16);
+    }
+}
+
+/// Drops the first `columns` characters of a line, for sideways scrolling.
+fn skip_columns(line: Line<'static>, columns: usize) -> Line<'static> {
+    let mut remaining = columns;
+    let spans = line
+        .spans
+        .into_iter()
+        .filter_map(|span| {
+            let length = span.content.chars().count();
+
+            if remaining >= length {
+                remaining -= length;
+                return None;
+            }
+
+            let content: String = span.content.chars().skip(remaining).collect();
+            remaining = 0;
+            Some(Span::styled(content, span.style))
+        })
+        .collect::<Vec<_>>();
+
+    Line::from(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fences_split_prose_from_code_and_charts() {
+        let document =
+            Document::parse("intro\n```rust\nfn main() {}\n```\n```gauge raw\n50%\n```\noutro");
+
+        assert!(matches!(
+            document.sections.as_slice(),
+            [
+                Section::Prose(intro),
+                Section::Code {
+                    language: "rust",
+                    lines: code,
+                },
+                Section::Chart { raw: true, .. },
+                Section::Prose(outro),
+            ] if intro == &["intro"] && code == &["fn main() {}"] && outro == &["outro"]
+        ));
+    }
+
+    #[test]
+    fn a_separator_row_makes_the_first_row_a_header() {
+        let document = Document::parse("| name | count |\n|:--|--:|\n| rust | 3 |");
+        let [Section::Table {
+            header,
+            alignments,
+            rows,
+        }] = document.sections.as_slice()
+        else {
+            panic!("expected a table, got {:?}", document.sections);
+        };
+
+        assert_eq!(header.as_deref(), Some(&["name", "count"][..]));
+        assert_eq!(alignments, &[Alignment::Left, Alignment::Right]);
+        assert_eq!(rows, &[vec!["rust", "3"]]);
+    }
+
+    #[test]
+    fn tables_without_a_separator_have_no_header() {
+        let document = Document::parse("| a | b |\n| c | d |");
+
+        assert!(matches!(
+            document.sections.as_slice(),
+            [Section::Table { header: None, rows, .. }] if rows.len() == 2
+        ));
+    }
+
+    #[test]
+    fn the_widest_columns_give_way_first() {
+        let rows = [vec!["a", "abcdefghij"], vec!["abcd", "ab"]];
+
+        assert_eq!(column_widths(None, &rows, 20), [4, 10]);
+        assert_eq!(column_widths(None, &rows, 10), [4, 5]);
+        assert_eq!(
+            column_widths(None, &rows, 2),
+            [MIN_COLUMN_WIDTH, MIN_COLUMN_WIDTH]
+        );
+    }
+
+    #[test]
+    fn truncated_cells_end_in_an_ellipsis() {
+        assert_eq!(truncate("ducktongue", 5), "duck…");
+        assert_eq!(truncate("duck", 5), "duck");
+    }
+
+    #[test]
+    fn code_overflows_by_its_longest_line() {
+        let document = Document::parse("```\n0123456789\n```");
+
+        // The gutter of a one line block is two columns wide.
+        assert_eq!(document.overflow(12), 0);
+        assert_eq!(document.overflow(8), 4);
+        assert_eq!(document.height(80), 3);
+    }
+}
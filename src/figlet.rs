@@ -0,0 +1,385 @@
+//! FIGlet banners.
+//!
+//! Parses `.flf` fonts and lays text out with them the way `figlet` does:
+//! full width, kerning, or smushing with the font's horizontal rules. The
+//! bundled fonts live under `assets/fonts`.
+
+use std::{collections::HashMap, fmt};
+
+pub const FONTS: &[(&str, &str)] = &[
+    ("mini", include_str!("../assets/fonts/mini.flf")),
+    ("block", include_str!("../assets/fonts/block.flf")),
+];
+
+/// The characters every font has to define, in order after the comments:
+/// printable ASCII followed by the seven Deutsch characters.
+const REQUIRED: &[u32] = &[196, 214, 220, 228, 246, 252, 223];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FontError {
+    MissingHeader,
+    BadHeader(String),
+    Truncated { code: u32 },
+}
+
+impl fmt::Display for FontError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FontError::MissingHeader => write!(f, "not a FIGlet font: missing flf2a header"),
+            FontError::BadHeader(header) => write!(f, "malformed FIGlet header: {header}"),
+            FontError::Truncated { code } => write!(f, "font ends inside character {code}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    FullWidth,
+    Kerning,
+    /// Controlled smushing with the given rule bits, universal smushing
+    /// when none are set.
+    Smushing(u32),
+}
+
+impl Layout {
+    fn new(old_layout: i32, full_layout: Option<u32>) -> Self {
+        match full_layout {
+            Some(full) if full & 128 != 0 => Layout::Smushing(full & 63),
+            Some(full) if full & 64 != 0 => Layout::Kerning,
+            Some(_) => Layout::FullWidth,
+            None if old_layout < 0 => Layout::FullWidth,
+            None if old_layout == 0 => Layout::Kerning,
+            None => Layout::Smushing(old_layout as u32 & 63),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Font {
+    hardblank: char,
+    height: usize,
+    layout: Layout,
+    glyphs: HashMap<char, Vec<Vec<char>>>,
+}
+
+impl Font {
+    pub fn bundled(name: &str) -> Option<Font> {
+        let (_, source) = FONTS.iter().find(|(font, _)| *font == name)?;
+
+        Font::parse(source).ok()
+    }
+
+    pub fn parse(source: &str) -> Result<Font, FontError> {
+        let mut lines = source.lines();
+        let header = lines.next().ok_or(FontError::MissingHeader)?;
+        let signature = header
+            .strip_prefix("flf2a")
+            .ok_or(FontError::MissingHeader)?;
+
+        let bad_header = || FontError::BadHeader(header.to_string());
+        let mut chars = signature.chars();
+        let hardblank = chars.next().ok_or_else(bad_header)?;
+        let fields: Vec<i64> = chars
+            .as_str()
+            .split_whitespace()
+            .map(|field| field.parse().map_err(|_| bad_header()))
+            .collect::<Result<_, _>>()?;
+
+        let [height, _baseline, _max_length, old_layout, comment_lines, ..] = fields[..] else {
+            return Err(bad_header());
+        };
+        let full_layout = fields.get(6).map(|layout| *layout as u32);
+
+        if height < 1 {
+            return Err(bad_header());
+        }
+        let height = height as usize;
+
+        let mut lines = lines.skip(comment_lines.max(0) as usize);
+        let mut glyphs = HashMap::new();
+
+        let mut read_glyph = |lines: &mut dyn Iterator<Item = &str>, code: u32| {
+            let rows = (0..height)
+                .map(|row| {
+                    lines
+                        .next()
+                        .map(|line| strip_endmark(line, row + 1 == height))
+                })
+                .collect::<Option<Vec<_>>>()
+                .ok_or(FontError::Truncated { code })?;
+
+            if let Some(glyph) = char::from_u32(code) {
+                glyphs.insert(glyph, rows);
+            }
+            Ok(())
+        };
+
+        for code in (32..127).chain(REQUIRED.iter().copied()) {
+            match read_glyph(&mut lines, code) {
+                // Old fonts stop after ASCII.
+                Err(_) if code > 126 => break,
+                result => result?,
+            }
+        }
+
+        while let Some(tag) = lines.next() {
+            let Some(code) = tag.split_whitespace().next().and_then(parse_code) else {
+                break;
+            };
+            read_glyph(&mut lines, code)?;
+        }
+
+        Ok(Font {
+            hardblank,
+            height,
+            layout: Layout::new(old_layout as i32, full_layout),
+            glyphs,
+        })
+    }
+
+    /// Lays `text` out in rows, one block of `height` rows per line of
+    /// text. Characters the font lacks are skipped.
+    pub fn render(&self, text: &str) -> Vec<String> {
+        text.split('\n')
+            .flat_map(|line| self.render_line(line))
+            .collect()
+    }
+
+    fn render_line(&self, text: &str) -> Vec<String> {
+        let mut rows: Vec<Vec<char>> = vec![Vec::new(); self.height];
+        let mut previous_width = 0;
+
+        for glyph in text.chars().filter_map(|letter| self.glyphs.get(&letter)) {
+            let width = glyph.first().map_or(0, Vec::len);
+            let overlap = self.overlap(&rows, glyph, previous_width);
+
+            for (row, glyph_row) in rows.iter_mut().zip(glyph) {
+                let start = row.len() - overlap;
+
+                for (column, right) in glyph_row.iter().enumerate() {
+                    match row.get_mut(start + column) {
+                        Some(left) => {
+                            *left = self
+                                .smush(*left, *right, previous_width, width)
+                                .unwrap_or(*right);
+                        }
+                        None => row.push(*right),
+                    }
+                }
+            }
+
+            // Keep the rows flush so the next glyph lines up with all of them.
+            let length = rows.iter().map(Vec::len).max().unwrap_or(0);
+            rows.iter_mut().for_each(|row| row.resize(length, ' '));
+
+            previous_width = width;
+        }
+
+        rows.into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|letter| {
+                        if letter == self.hardblank {
+                            ' '
+                        } else {
+                            letter
+                        }
+                    })
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    /// How many columns `glyph` can move left into the rows laid out so
+    /// far, as in `figlet`'s `smushamt`.
+    fn overlap(&self, rows: &[Vec<char>], glyph: &[Vec<char>], previous_width: usize) -> usize {
+        if self.layout == Layout::FullWidth {
+            return 0;
+        }
+
+        let width = glyph.first().map_or(0, Vec::len);
+
+        rows.iter()
+            .zip(glyph)
+            .map(|(row, glyph_row)| {
+                let line_edge = row.iter().rposition(|letter| *letter != ' ');
+                let glyph_edge = glyph_row
+                    .iter()
+                    .position(|letter| *letter != ' ')
+                    .unwrap_or(width);
+
+                let Some(line_edge) = line_edge else {
+                    return row.len() + glyph_edge;
+                };
+
+                let amount = glyph_edge + row.len() - 1 - line_edge;
+                let left = row[line_edge];
+                let right = glyph_row.get(glyph_edge);
+
+                match right {
+                    Some(right) if self.smush(left, *right, previous_width, width).is_some() => {
+                        amount + 1
+                    }
+                    _ => amount,
+                }
+            })
+            .min()
+            .unwrap_or(0)
+            .min(width)
+            .min(rows.iter().map(Vec::len).min().unwrap_or(0))
+    }
+
+    /// The character two overlapping characters become, if they may
+    /// overlap at all.
+    fn smush(
+        &self,
+        left: char,
+        right: char,
+        left_width: usize,
+        right_width: usize,
+    ) -> Option<char> {
+        if left == ' ' {
+            return Some(right);
+        }
+        if right == ' ' {
+            return Some(left);
+        }
+
+        let Layout::Smushing(rules) = self.layout else {
+            return None;
+        };
+
+        if left_width < 2 || right_width < 2 {
+            return None;
+        }
+
+        if rules == 0 {
+            return Some(match (left == self.hardblank, right == self.hardblank) {
+                (_, true) => left,
+                _ => right,
+            });
+        }
+
+        if left == self.hardblank || right == self.hardblank {
+            return (rules & 32 != 0 && left == right).then_some(left);
+        }
+
+        if rules & 1 != 0 && left == right {
+            return Some(left);
+        }
+
+        if rules & 2 != 0 {
+            const BORDERS: &str = "|/\\[]{}()<>";
+
+            if left == '_' && BORDERS.contains(right) {
+                return Some(right);
+            }
+            if right == '_' && BORDERS.contains(left) {
+                return Some(left);
+            }
+        }
+
+        if rules & 4 != 0 {
+            const CLASSES: [&str; 6] = ["|", "/\\", "[]", "{}", "()", "<>"];
+            let class = |letter| CLASSES.iter().position(|class| class.contains(letter));
+
+            if let (Some(left_class), Some(right_class)) = (class(left), class(right)) {
+                if left_class != right_class {
+                    return Some(if left_class > right_class {
+                        left
+                    } else {
+                        right
+                    });
+                }
+            }
+        }
+
+        if rules & 8 != 0
+            && matches!(
+                (left, right),
+                ('[', ']') | (']', '[') | ('{', '}') | ('}', '{') | ('(', ')') | (')', '(')
+            )
+        {
+            return Some('|');
+        }
+
+        if rules & 16 != 0 {
+            match (left, right) {
+                ('/', '\\') => return Some('|'),
+                ('\\', '/') => return Some('Y'),
+                ('>', '<') => return Some('X'),
+                _ => {}
+            }
+        }
+
+        None
+    }
+}
+
+/// Removes the endmark, the last character of a glyph row, once or, on
+/// the last row, twice. Glyph art ending in the endmark character itself
+/// is left alone.
+fn strip_endmark(row: &str, last: bool) -> Vec<char> {
+    let mut chars: Vec<char> = row.trim_end().chars().collect();
+    let Some(endmark) = chars.pop() else {
+        return chars;
+    };
+
+    if last && chars.last() == Some(&endmark) {
+        chars.pop();
+    }
+    chars
+}
+
+/// Code tags are decimal, hexadecimal with `0x` or octal with a leading
+/// zero. Negative codes are font specific and not supported.
+fn parse_code(tag: &str) -> Option<u32> {
+    if let Some(hex) = tag.strip_prefix("0x").or_else(|| tag.strip_prefix("0X")) {
+        u32::from_str_radix(hex, 16).ok()
+    } else if tag.len() > 1 && tag.starts_with('0') {
+        u32::from_str_radix(&tag[1..], 8).ok()
+    } else {
+        tag.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A two row font where every glyph is `x`, except `!`, drawn with the
+    /// endmark character itself.
+    fn font() -> Font {
+        let mut source = String::from("flf2a$ 2 1 4 -1 0\n");
+        for code in 32..127 {
+            let art = if code == u32::from('!') { "a@" } else { "x" };
+            source.push_str(&format!("{art}@\n{art}@@\n"));
+        }
+
+        Font::parse(&source).unwrap()
+    }
+
+    #[test]
+    fn strips_one_endmark_and_two_on_the_last_row() {
+        assert_eq!(strip_endmark("ab@", false), vec!['a', 'b']);
+        assert_eq!(strip_endmark("ab@@", true), vec!['a', 'b']);
+    }
+
+    #[test]
+    fn keeps_art_ending_in_the_endmark() {
+        assert_eq!(font().render("!"), vec!["a@", "a@"]);
+    }
+
+    #[test]
+    fn bundled_fonts_parse() {
+        for (name, _) in FONTS {
+            let font = Font::bundled(name).unwrap();
+            assert!(font
+                .render("tarbetu")
+                .iter()
+                .any(|row| !row.trim().is_empty()));
+        }
+    }
+}
@@ -0,0 +1,167 @@
+//! Focus stack of the list view.
+//!
+//! The menu always sits at the bottom of the stack. Entering a page pushes
+//! the content layer, overlays are pushed on top of whatever is focused and
+//! Esc pops exactly one layer. Only the topmost layer receives key events.
+//!
+//! Each layer keeps its key handling and rendering in a module of its own
+//! below, as methods of the [`App`](crate::App) whose state they work on.
+
+mod content;
+mod game;
+mod menu;
+mod overlay;
+mod prompt;
+
+use crate::keymap::{Action, Context, Preset};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Layer {
+    Menu,
+    Content,
+    /// Typing into the prompt of the page, which sees every key first and
+    /// leaves the keymap only what it has no use for.
+    Prompt,
+    /// Reading the book of the page, with the motions of the pane moving
+    /// through its contents and chapters instead of the prose.
+    Reader,
+    Game,
+    Overlay(Overlay),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Overlay {
+    Popup {
+        title: &'static str,
+        message: &'static str,
+    },
+    Help {
+        scroll: u16,
+    },
+    Paused,
+}
+
+/// What a prompt, the reader or any other widget of a page did with a key
+/// or action handed to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reply {
+    /// Edited the input, or moved around within the widget.
+    Consumed,
+    /// Ran what the visitor typed.
+    Ran,
+    /// Quit; the page should close. The next key typed starts over.
+    Quit,
+    /// Not a key for the widget, left to the pager.
+    Ignored,
+}
+
+impl Layer {
+    /// Footer hints shown while this layer is focused, spelled out in
+    /// the keys of the active preset.
+    pub fn hints(&self, preset: Preset) -> String {
+        use Action::*;
+
+        match self {
+            Layer::Menu => format!(
+                "Use {} and {} to navigate, {} and {} to fold, {} to locked in, {} for help",
+                preset.keys(Context::Menu, Previous),
+                preset.keys(Context::Menu, Next),
+                preset.keys(Context::Menu, Expand),
+                preset.keys(Context::Menu, Collapse),
+                preset.keys(Context::Menu, Select),
+                preset.keys(Context::Menu, Help),
+            ),
+            Layer::Content => format!(
+                "Use {} and {} to scroll, {} and {} to page, {} to return menu, {} for help",
+                preset.keys(Context::Content, ScrollUp),
+                preset.keys(Context::Content, ScrollDown),
+                preset.keys(Context::Content, PageUp),
+                preset.keys(Context::Content, PageDown),
+                preset.keys(Context::Content, Back),
+                preset.keys(Context::Content, Help),
+            ),
+            Layer::Prompt => format!(
+                "Type away, {} to stop typing, {} for help",
+                preset.keys(Context::Prompt, Back),
+                preset.keys(Context::Prompt, Help),
+            ),
+            Layer::Reader => format!(
+                "Use {} and {} to read, {} to open, {} for the contents, {} to stop reading",
+                preset.keys(Context::Content, ScrollUp),
+                preset.keys(Context::Content, ScrollDown),
+                preset.keys(Context::Content, Select),
+                preset.keys(Context::Content, Contents),
+                preset.keys(Context::Content, Back),
+            ),
+            Layer::Game => format!(
+                "Use {} {} {} {} to move, {} to drop, {} to pause",
+                preset.keys(Context::Game, MoveUp),
+                preset.keys(Context::Game, MoveDown),
+                preset.keys(Context::Game, MoveLeft),
+                preset.keys(Context::Game, MoveRight),
+                preset.keys(Context::Game, Drop),
+                preset.keys(Context::Game, Pause),
+            ),
+            Layer::Overlay(Overlay::Popup { .. }) => {
+                format!("Press {} to close", preset.keys(Context::Overlay, Back))
+            }
+            Layer::Overlay(Overlay::Paused) => format!(
+                "Press {} to resume, {} to quit the game",
+                preset.keys(Context::Overlay, Back),
+                preset.keys(Context::Overlay, Quit),
+            ),
+            Layer::Overlay(Overlay::Help { .. }) => format!(
+                "Use {} and {} to scroll, {} to switch keymap, {} to close",
+                preset.keys(Context::Overlay, ScrollUp),
+                preset.keys(Context::Overlay, ScrollDown),
+                preset.keys(Context::Overlay, NextPreset),
+                preset.keys(Context::Overlay, Back),
+            ),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FocusStack {
+    layers: Vec<Layer>,
+}
+
+impl Default for FocusStack {
+    fn default() -> Self {
+        Self {
+            layers: vec![Layer::Menu],
+        }
+    }
+}
+
+impl FocusStack {
+    pub fn active(&self) -> &Layer {
+        self.layers.last().unwrap_or(&Layer::Menu)
+    }
+
+    pub fn active_mut(&mut self) -> Option<&mut Layer> {
+        self.layers.last_mut()
+    }
+
+    pub fn push(&mut self, layer: Layer) {
+        self.layers.push(layer);
+    }
+
+    /// Pops the focused layer. The menu is never popped.
+    pub fn pop(&mut self) {
+        if self.layers.len() > 1 {
+            self.layers.pop();
+        }
+    }
+
+    pub fn contains(&self, layer: &Layer) -> bool {
+        self.layers.contains(layer)
+    }
+
+    pub fn overlays(&self) -> impl Iterator<Item = &Overlay> {
+        self.layers.iter().filter_map(|layer| match layer {
+            Layer::Overlay(overlay) => Some(overlay),
+            _ => None,
+        })
+    }
+}
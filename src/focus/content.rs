@@ -0,0 +1,409 @@
+//! The content layer: reading, scrolling and playing with the page locked
+//! in, be it prose, the soundtrack, the arcade, a REPL or a book.
+
+use ratatui::{
+    prelude::*,
+    style::{Color, Stylize},
+    symbols::scrollbar,
+    widgets::*,
+    Frame,
+};
+use ratzilla::event::{KeyCode, KeyEvent};
+use web_time::Instant;
+
+use crate::{
+    dictionary::Dictionary,
+    document::Document,
+    focus::{Layer, Overlay},
+    keymap::{Action, Context},
+    menu::Entry,
+    raster::{self, Raster},
+    reader::Reader,
+    repl::Repl,
+    vfs, App,
+};
+
+impl App {
+    pub(crate) fn handle_content_event(&mut self, event: KeyEvent) {
+        let action = self.keymap.action(Context::Content, &event);
+
+        if let (Some(Action::Count), KeyCode::Char(digit)) = (action, event.code) {
+            if digit != '0' || self.count.is_some() {
+                let digit = digit.to_digit(10).unwrap_or_default() as u16;
+                self.count = Some(
+                    self.count
+                        .unwrap_or_default()
+                        .saturating_mul(10)
+                        .saturating_add(digit),
+                );
+                return;
+            }
+        }
+
+        let count = self.count.take();
+        let times = count.unwrap_or(1);
+        let page = self.page_height.max(1);
+        let half_page = (page / 2).max(1);
+
+        if self.handle_secret(&event) {
+            return;
+        }
+
+        if self.selected_page() == vfs::MUSIC_PAGE {
+            let soundtrack = &mut self.soundtrack;
+
+            match action {
+                Some(Action::ScrollUp) => return soundtrack.select_previous(times),
+                Some(Action::ScrollDown) => return soundtrack.select_next(times),
+                Some(Action::HalfPageUp) => return soundtrack.select_previous(half_page * times),
+                Some(Action::HalfPageDown) => return soundtrack.select_next(half_page * times),
+                Some(Action::PageUp) => return soundtrack.select_previous(page * times),
+                Some(Action::PageDown) => return soundtrack.select_next(page * times),
+                Some(Action::Top) => return soundtrack.select_first(),
+                Some(Action::Bottom) => return soundtrack.select_last(),
+                Some(Action::Sort) => return soundtrack.next_sort(),
+                Some(Action::Filter) => return soundtrack.next_genre(),
+                Some(Action::Group) => return soundtrack.toggle_grouped(),
+                Some(Action::Random) => return soundtrack.pick_random(),
+                _ => {}
+            }
+        }
+
+        // While reading, the motions belong to the book and the prose
+        // stays where it was left.
+        if self.focus.active() == &Layer::Reader {
+            match (action, self.reader_mut()) {
+                (Some(Action::Back), _) => self.focus.pop(),
+                (Some(Action::Help), _) => {
+                    self.focus.push(Layer::Overlay(Overlay::Help { scroll: 0 }))
+                }
+                (Some(action), Some(reader)) => {
+                    reader.handle(action, times);
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        if self.selected_page() == vfs::GAMES_PAGE {
+            match action {
+                Some(Action::ScrollUp) => return self.arcade.select_previous(),
+                Some(Action::ScrollDown) => return self.arcade.select_next(),
+                Some(Action::Select) => {
+                    self.arcade.start(Instant::now());
+                    return self.focus.push(Layer::Game);
+                }
+                _ => {}
+            }
+        }
+
+        match action {
+            Some(Action::Back) => self.focus.pop(),
+            Some(Action::Select) if self.has_reader() => {
+                self.focus.push(Layer::Reader);
+            }
+            Some(Action::Select) if self.has_prompt() => {
+                self.focus.push(Layer::Prompt);
+                self.follow_prompt = self.selected_page() == vfs::ABOUT_PAGE;
+            }
+            Some(Action::HalfPageDown) => self.scroll_down(half_page.saturating_mul(times)),
+            Some(Action::HalfPageUp) => self.scroll_up(half_page.saturating_mul(times)),
+            Some(Action::ScrollUp) => self.scroll_up(times),
+            Some(Action::ScrollDown) => self.scroll_down(times),
+            Some(Action::ScrollLeft) => {
+                let columns = App::HORIZONTAL_STEP.saturating_mul(times);
+                self.horizontal_scroll = self.horizontal_scroll.saturating_sub(columns);
+            }
+            Some(Action::ScrollRight) => {
+                let columns = App::HORIZONTAL_STEP.saturating_mul(times);
+                self.horizontal_scroll =
+                    (self.horizontal_scroll.saturating_add(columns)).min(self.code_overflow);
+            }
+            Some(Action::PageUp) => self.scroll_up(page.saturating_mul(times)),
+            Some(Action::PageDown) => self.scroll_down(page.saturating_mul(times)),
+            Some(Action::Top) => {
+                self.scroll = count.map_or(0, |line| line.saturating_sub(1));
+                self.scroll = self.scroll.min(self.max_scroll());
+            }
+            Some(Action::Bottom) => {
+                self.scroll = count.map_or(self.max_scroll(), |line| {
+                    line.saturating_sub(1).min(self.max_scroll())
+                });
+                self.read_if_at_bottom();
+            }
+            Some(Action::Help) => self.focus.push(Layer::Overlay(Overlay::Help { scroll: 0 })),
+            _ => {}
+        }
+    }
+
+    /// Whether the page has a prompt to type into: gdb, a REPL or the
+    /// dictionary.
+    pub(crate) fn has_prompt(&self) -> bool {
+        matches!(
+            self.selected_entry(),
+            Entry {
+                page: vfs::ABOUT_PAGE,
+                ..
+            } | Entry {
+                page: vfs::LYCIAN_PAGE,
+                subpage: None,
+            } | Entry {
+                page: vfs::PORTFOLIO_PAGE,
+                subpage: Some(App::TARLOX_SUBPAGE | App::DUCKTONGUE_SUBPAGE),
+            }
+        )
+    }
+
+    /// The REPL of the selected page, for pages about a language.
+    pub(crate) fn repl_mut(&mut self) -> Option<&mut Repl> {
+        match self.selected_entry() {
+            Entry {
+                page: vfs::LYCIAN_PAGE,
+                subpage: None,
+            } => Some(&mut self.lisp),
+            Entry {
+                page: vfs::PORTFOLIO_PAGE,
+                subpage: Some(App::TARLOX_SUBPAGE),
+            } => Some(&mut self.lox),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn dictionary_mut(&mut self) -> Option<&mut Dictionary> {
+        match self.selected_entry() {
+            Entry {
+                page: vfs::PORTFOLIO_PAGE,
+                subpage: Some(App::DUCKTONGUE_SUBPAGE),
+            } => Some(&mut self.dictionary),
+            _ => None,
+        }
+    }
+
+    /// The reader of the selected page, for pages with a book of the same
+    /// name under `assets/books`.
+    pub(crate) fn reader_mut(&mut self) -> Option<&mut Reader> {
+        let name = self.selected_entry().node()?.name;
+
+        self.readers.iter_mut().find(|reader| reader.id() == name)
+    }
+
+    /// Whether the page has a book to read.
+    pub(crate) fn has_reader(&self) -> bool {
+        let Some(node) = self.selected_entry().node() else {
+            return false;
+        };
+
+        self.readers.iter().any(|reader| reader.id() == node.name)
+    }
+
+    /// The last line that can be at the top of the content pane
+    /// while the pane is still filled, in the manner of `less`.
+    pub(crate) fn max_scroll(&self) -> u16 {
+        self.content_length.saturating_sub(self.page_height)
+    }
+
+    pub(crate) fn scroll_up(&mut self, lines: u16) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    pub(crate) fn scroll_down(&mut self, lines: u16) {
+        self.scroll = self.scroll.saturating_add(lines).min(self.max_scroll());
+        self.read_if_at_bottom();
+    }
+
+    /// Counts the page as read once the visitor scrolls down to its last
+    /// line. Pages that fit the pane whole count on the first motion down,
+    /// never on merely being opened.
+    pub(crate) fn read_if_at_bottom(&mut self) {
+        let entry = self.selected_entry();
+
+        if entry.page < App::READABLE_PAGES && self.scroll == self.max_scroll() {
+            self.achievements
+                .read_page(entry.slot(), App::READABLE_SLOTS, Instant::now());
+        }
+    }
+
+    pub(crate) fn render_content(&mut self, frame: &mut Frame, area: Rect) {
+        let content_block = Block::bordered()
+            .border_set(if self.focus.contains(&Layer::Content) {
+                symbols::border::QUADRANT_OUTSIDE
+            } else {
+                symbols::border::EMPTY
+            })
+            .border_style(Style::default().fg(Color::LightMagenta))
+            .bg(Color::Rgb(15, 15, 20))
+            .padding(Padding::new(1, 2, 0, 0))
+            .fg(Color::LightCyan);
+
+        if self.selected_page() == vfs::MUSIC_PAGE {
+            let inner_area = content_block.inner(area);
+
+            self.page_height = inner_area.height;
+            frame.render_widget(content_block, area);
+            self.soundtrack.render(inner_area, frame.buffer_mut());
+
+            if self.focus.contains(&Layer::Content) && self.soundtrack.is_at_end() {
+                self.achievements
+                    .read_page(vfs::MUSIC_PAGE, App::READABLE_SLOTS, Instant::now());
+            }
+            return;
+        }
+
+        if self.selected_page() == vfs::GAMES_PAGE {
+            let inner_area = content_block.inner(area);
+
+            frame.render_widget(content_block, area);
+            self.arcade.render(inner_area, frame.buffer_mut());
+            return;
+        }
+
+        let text = match self.selected_page() {
+            vfs::ABOUT_PAGE => self.gdb.text(),
+            vfs::ACHIEVEMENTS_PAGE => self.achievements.page(),
+            _ => self
+                .selected_entry()
+                .node()
+                .and_then(|node| node.text())
+                .unwrap_or_default(),
+        };
+        let text = text.as_str();
+
+        // Books keep their reader under the prose, and an open chapter takes
+        // the whole pane.
+        let keymap = self.keymap;
+        let area = match self.reader_mut() {
+            Some(reader) => {
+                let reader_block = content_block.clone().title(format!(" {} ", reader.title()));
+                let [text_area, reader_area] = match reader.is_reading() {
+                    true => [Rect::default(), area],
+                    false => App::below_prose(area),
+                };
+                let inner_area = reader_block.inner(reader_area);
+
+                frame.render_widget(reader_block, reader_area);
+                reader.render(inner_area, frame.buffer_mut(), keymap);
+                if text_area.is_empty() {
+                    return;
+                }
+                text_area
+            }
+            None => area,
+        };
+
+        let illustration = match self.selected_page() {
+            vfs::KTH_PAGE => raster::image("kara_tilki"),
+            _ => None,
+        };
+
+        // Illustrations only get a column when the prose keeps enough room.
+        if let Some(image) = illustration.filter(|_| area.width >= 80) {
+            let [text_area, image_area] =
+                Layout::horizontal([Constraint::Fill(1), Constraint::Length(32)]).areas(area);
+            let image_block = content_block.clone();
+            let inner_area = image_block.inner(image_area);
+
+            self.render_text(frame, content_block, text_area, text);
+            frame.render_widget(image_block, image_area);
+            frame.render_widget(
+                Raster::new(image).auto_mode(inner_area, self.monochrome),
+                inner_area,
+            );
+            return;
+        }
+
+        // Language pages keep their REPL under the prose.
+        if let Some(repl) = self.repl_mut() {
+            let [text_area, repl_area] = App::below_prose(area);
+            let repl_block = content_block.clone().title(format!(" {} ", repl.name()));
+            let inner_area = repl_block.inner(repl_area);
+
+            frame.render_widget(repl_block, repl_area);
+            frame.render_widget(&*repl, inner_area);
+            self.render_text(frame, content_block, text_area, text);
+            return;
+        }
+
+        if let Some(dictionary) = self.dictionary_mut() {
+            let [text_area, dictionary_area] = App::below_prose(area);
+            let dictionary_block = content_block.clone().title(" ducktongue ");
+            let inner_area = dictionary_block.inner(dictionary_area);
+
+            frame.render_widget(dictionary_block, dictionary_area);
+            frame.render_widget(&*dictionary, inner_area);
+            self.render_text(frame, content_block, text_area, text);
+            return;
+        }
+
+        let banner_width = self.banner.iter().map(|row| row.chars().count()).max();
+        let banner_fits =
+            banner_width.is_some_and(|width| width <= content_block.inner(area).width as usize);
+
+        // The banner sits in the block's top padding, above the scrolling text.
+        if self.selected_page() == vfs::ABOUT_PAGE && banner_fits {
+            let banner_height = self.banner.len() as u16;
+            let content_block = content_block.padding(Padding::new(1, 2, banner_height + 1, 0));
+            let inner_area = content_block.inner(area);
+            let banner_area = Rect {
+                y: inner_area.y.saturating_sub(banner_height + 1),
+                height: banner_height,
+                ..inner_area
+            };
+
+            self.render_text(frame, content_block, area, text);
+            frame.render_widget(
+                Text::from_iter(self.banner.iter().map(String::as_str)).light_magenta(),
+                banner_area,
+            );
+            return;
+        }
+
+        self.render_text(frame, content_block, area, text);
+    }
+
+    /// Splits the content pane between the prose of a page and the widget
+    /// under it, giving the widget all of it when there is no room for
+    /// both.
+    pub(crate) fn below_prose(area: Rect) -> [Rect; 2] {
+        let constraints = if area.height >= App::WIDGET_MIN_HEIGHT {
+            [Constraint::Fill(1), Constraint::Percentage(50)]
+        } else {
+            [Constraint::Length(0), Constraint::Fill(1)]
+        };
+
+        Layout::vertical(constraints).areas(area)
+    }
+
+    pub(crate) fn render_text(&mut self, frame: &mut Frame, block: Block, area: Rect, text: &str) {
+        let document = Document::parse(text);
+
+        let inner_area = block.inner(area);
+        self.page_height = inner_area.height;
+        self.content_length = document.height(inner_area.width);
+        self.content_slot = self.selected_entry().slot();
+        self.scroll = match std::mem::take(&mut self.follow_prompt) {
+            true => self.max_scroll(),
+            false => self.scroll.min(self.max_scroll()),
+        };
+        self.code_overflow = document.overflow(inner_area.width);
+        self.horizontal_scroll = self.horizontal_scroll.min(self.code_overflow);
+
+        self.scrollbar_state = self
+            .scrollbar_state
+            .content_length(self.max_scroll() as usize + 1)
+            .position(self.scroll as usize);
+
+        frame.render_widget(block, area);
+        document.render(
+            inner_area,
+            frame.buffer_mut(),
+            self.scroll,
+            self.horizontal_scroll,
+        );
+
+        frame.render_stateful_widget(
+            Scrollbar::new(ScrollbarOrientation::VerticalRight).symbols(scrollbar::VERTICAL),
+            area,
+            &mut self.scrollbar_state,
+        );
+    }
+}
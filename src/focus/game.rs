@@ -0,0 +1,20 @@
+//! The game layer: playing the game started from the games page.
+
+use ratzilla::event::KeyEvent;
+use web_time::Instant;
+
+use crate::{
+    focus::{Layer, Overlay},
+    keymap::{Action, Context},
+    App,
+};
+
+impl App {
+    pub(crate) fn handle_game_event(&mut self, event: KeyEvent) {
+        match self.keymap.action(Context::Game, &event) {
+            Some(Action::Pause) => self.focus.push(Layer::Overlay(Overlay::Paused)),
+            Some(action) => self.arcade.handle(action, Instant::now()),
+            None => {}
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! The menu layer: picking a page, and folding and unfolding subpages.
+
+use ratatui::{
+    prelude::*,
+    style::{Color, Stylize},
+    widgets::*,
+    Frame,
+};
+use ratzilla::event::KeyEvent;
+
+use crate::{
+    focus::{Layer, Overlay},
+    keymap::{Action, Context},
+    App,
+};
+
+impl App {
+    pub(crate) fn handle_menu_event(&mut self, event: KeyEvent) {
+        if self.handle_secret(&event) {
+            return;
+        }
+
+        match self.keymap.action(Context::Menu, &event) {
+            Some(Action::Select) => self.focus.push(Layer::Content),
+            Some(Action::Previous) => {
+                self.tree.select_previous(self.page_count());
+                self.page_changed();
+            }
+            Some(Action::Next) => {
+                self.tree.select_next(self.page_count());
+                self.page_changed();
+            }
+            Some(Action::Expand) => {
+                if self.tree.expand(self.page_count()) {
+                    self.page_changed();
+                } else {
+                    self.focus.push(Layer::Content);
+                }
+            }
+            Some(Action::Collapse) if self.tree.collapse(self.page_count()) => {
+                self.page_changed();
+            }
+            Some(Action::Help) => self.focus.push(Layer::Overlay(Overlay::Help { scroll: 0 })),
+            Some(Action::NextPreset) => self.next_keymap(),
+            _ => {}
+        }
+    }
+
+    pub(crate) fn render_list(&mut self, frame: &mut Frame, area: Rect) {
+        let locked_in = self.focus.contains(&Layer::Content);
+
+        let list_block = Block::bordered()
+            .border_set(if !locked_in {
+                symbols::border::QUADRANT_OUTSIDE
+            } else {
+                symbols::border::EMPTY
+            })
+            .border_style(Style::default().fg(Color::LightMagenta))
+            .bg(Color::Rgb(15, 15, 20))
+            .fg(if !locked_in {
+                Color::LightCyan
+            } else {
+                Color::Cyan
+            });
+
+        let list = List::new(self.tree.items(self.page_count()))
+            .block(list_block)
+            .highlight_style(Style::default().fg(Color::LightMagenta))
+            .highlight_symbol("▶ ")
+            .highlight_spacing(HighlightSpacing::Always);
+
+        frame.render_stateful_widget(list, area, self.tree.state_mut());
+    }
+}
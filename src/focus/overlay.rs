@@ -0,0 +1,172 @@
+//! The overlay layer: popups, the pause screen and the help.
+
+use ratatui::{
+    layout::Alignment,
+    prelude::*,
+    style::{Color, Stylize},
+    widgets::*,
+    Frame,
+};
+use ratzilla::event::KeyEvent;
+use web_time::Instant;
+
+use crate::{
+    focus::{Layer, Overlay},
+    keymap::{Action, Context},
+    App,
+};
+
+impl App {
+    pub(crate) fn handle_overlay_event(&mut self, event: KeyEvent) {
+        let action = self.keymap.action(Context::Overlay, &event);
+        let paused = self.focus.active() == &Layer::Overlay(Overlay::Paused);
+
+        if action == Some(Action::Back) {
+            self.focus.pop();
+
+            if paused {
+                self.arcade.resume(Instant::now());
+            }
+        } else if action == Some(Action::Quit) {
+            self.focus.pop();
+
+            if paused {
+                self.focus.pop();
+                self.arcade.stop();
+            }
+        } else if action == Some(Action::NextPreset) {
+            self.next_keymap();
+        } else if let Some(Layer::Overlay(Overlay::Help { scroll })) = self.focus.active_mut() {
+            match action {
+                Some(Action::ScrollUp) => *scroll = scroll.saturating_sub(1),
+                Some(Action::ScrollDown) => *scroll = scroll.saturating_add(1),
+                _ => {}
+            }
+        }
+    }
+
+    pub(crate) fn render_overlays(&mut self, frame: &mut Frame, area: Rect) {
+        // The help scrolls no further than its last line, border and
+        // padding aside.
+        let help_height = App::help_area(area).height.saturating_sub(4);
+        let help_bottom = (self.help_lines().len() as u16).saturating_sub(help_height);
+        if let Some(Layer::Overlay(Overlay::Help { scroll })) = self.focus.active_mut() {
+            *scroll = (*scroll).min(help_bottom);
+        }
+
+        for overlay in self.focus.overlays() {
+            match overlay {
+                Overlay::Popup { title, message } => {
+                    let popup_area =
+                        App::center(area, Constraint::Percentage(50), Constraint::Length(7));
+
+                    let popup = Paragraph::new(*message)
+                        .block(
+                            Block::bordered()
+                                .title(*title)
+                                .title_alignment(Alignment::Center)
+                                .border_set(symbols::border::QUADRANT_OUTSIDE)
+                                .border_style(Style::default().fg(Color::LightMagenta))
+                                .padding(Padding::uniform(1)),
+                        )
+                        .bg(Color::Rgb(15, 15, 20))
+                        .fg(Color::LightCyan)
+                        .wrap(Wrap { trim: true })
+                        .centered();
+
+                    frame.render_widget(Clear, popup_area);
+                    frame.render_widget(popup, popup_area);
+                }
+                Overlay::Paused => {
+                    let paused_area =
+                        App::center(area, Constraint::Length(30), Constraint::Length(5));
+
+                    let paused = Paragraph::new("Take a breath.")
+                        .block(
+                            Block::bordered()
+                                .title(" Paused ")
+                                .title_alignment(Alignment::Center)
+                                .border_set(symbols::border::QUADRANT_OUTSIDE)
+                                .border_style(Style::default().fg(Color::LightMagenta))
+                                .padding(Padding::uniform(1)),
+                        )
+                        .bg(Color::Rgb(15, 15, 20))
+                        .fg(Color::LightCyan)
+                        .centered();
+
+                    frame.render_widget(Clear, paused_area);
+                    frame.render_widget(paused, paused_area);
+                }
+                Overlay::Help { scroll } => {
+                    let help_area = App::help_area(area);
+
+                    let help = Paragraph::new(self.help_lines())
+                        .block(
+                            Block::bordered()
+                                .title(format!(" Keybindings ({}) ", self.keymap.name()))
+                                .title_alignment(Alignment::Center)
+                                .border_set(symbols::border::QUADRANT_OUTSIDE)
+                                .border_style(Style::default().fg(Color::LightMagenta))
+                                .padding(Padding::new(2, 2, 1, 1)),
+                        )
+                        .bg(Color::Rgb(15, 15, 20))
+                        .fg(Color::LightCyan)
+                        .scroll((*scroll, 0));
+
+                    frame.render_widget(Clear, help_area);
+                    frame.render_widget(help, help_area);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn help_area(area: Rect) -> Rect {
+        App::center(area, Constraint::Percentage(70), Constraint::Percentage(90))
+    }
+
+    pub(crate) fn help_lines(&self) -> Vec<Line<'static>> {
+        let mut lines = Vec::new();
+
+        if let Some(note) = self.keymap.note() {
+            lines.extend(note.lines().map(|line| Line::from(line).dark_gray()));
+            lines.push(Line::default());
+        }
+
+        for context in Context::ALL {
+            lines.push(Line::from(format!(">>> {}", context.title())).bold());
+
+            for binding in self.keymap.bindings(context) {
+                let chords = binding
+                    .chords
+                    .iter()
+                    .map(|chord| chord.label())
+                    .collect::<Vec<_>>()
+                    .join(" / ");
+
+                lines.push(Line::from(vec![
+                    format!("  {chords:<18}").light_magenta(),
+                    Span::from(binding.description),
+                ]));
+            }
+
+            lines.push(Line::default());
+        }
+
+        lines.push(Line::from(">>> Secrets").bold());
+
+        for (secret, found) in self.secrets.hints() {
+            let hint = if secret.public || found {
+                secret.trigger.label()
+            } else {
+                "???".into()
+            };
+
+            lines.push(Line::from(vec![
+                format!("  {hint:<18}").light_magenta(),
+                Span::from(if found { "Found" } else { "Not found yet" }),
+            ]));
+        }
+
+        lines
+    }
+}
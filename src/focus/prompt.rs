@@ -0,0 +1,54 @@
+//! The prompt layer: typing into gdb, a REPL or the dictionary.
+
+use ratzilla::event::KeyEvent;
+use web_time::Instant;
+
+use crate::{
+    focus::Reply,
+    focus::{Layer, Overlay},
+    keymap::{Action, Context},
+    vfs, App,
+};
+
+impl App {
+    /// Keys typed into the prompt of the page, with the keymap getting
+    /// only the ones the prompt ignores.
+    pub(crate) fn handle_prompt_event(&mut self, event: KeyEvent) {
+        let sudo = self.secrets.is_found("sudo");
+        let reply = if self.selected_page() == vfs::ABOUT_PAGE {
+            self.gdb.handle(&event, sudo)
+        } else if let Some(repl) = self.repl_mut() {
+            repl.handle(&event)
+        } else if let Some(dictionary) = self.dictionary_mut() {
+            dictionary.handle(&event)
+        } else {
+            Reply::Ignored
+        };
+
+        if self.selected_page() == vfs::ABOUT_PAGE && reply != Reply::Ignored {
+            // The gdb prompt sits at the bottom of the page it grows.
+            self.follow_prompt = true;
+        }
+
+        match reply {
+            Reply::Consumed => {}
+            Reply::Ran if self.selected_page() == vfs::ABOUT_PAGE => {
+                self.achievements.unlock("gdb", Instant::now());
+                if self.gdb.shelled_out() {
+                    self.achievements.unlock("shell", Instant::now());
+                }
+            }
+            Reply::Ran => {}
+            // Quitting closes the page along with the prompt.
+            Reply::Quit => {
+                self.focus.pop();
+                self.focus.pop();
+            }
+            Reply::Ignored => match self.keymap.action(Context::Prompt, &event) {
+                Some(Action::Back) => self.focus.pop(),
+                Some(Action::Help) => self.focus.push(Layer::Overlay(Overlay::Help { scroll: 0 })),
+                _ => {}
+            },
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! Terminal mini-games of the `./games` page.
+//!
+//! Each game implements [`Game`]; [`Arcade`] owns the picker, the running
+//! game, its tick clock and the high scores kept in `localStorage`.
+
+mod snake;
+mod tetris;
+mod twenty48;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Paragraph, Widget},
+};
+use web_time::{Duration, Instant};
+
+use crate::{keymap::Action, storage};
+
+pub trait Game: std::fmt::Debug {
+    /// How often [`Game::tick`] runs, `None` for turn based games.
+    fn tick_interval(&self) -> Option<Duration>;
+
+    fn tick(&mut self);
+
+    fn handle(&mut self, action: Action);
+
+    fn score(&self) -> u32;
+
+    fn is_over(&self) -> bool;
+
+    /// Whether the game ended because there was nothing left to win.
+    fn is_won(&self) -> bool {
+        false
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer);
+}
+
+const GAMES: [&str; 3] = ["snake", "tetris", "2048"];
+
+fn new_game(index: usize) -> Box<dyn Game> {
+    match index {
+        0 => Box::new(snake::Snake::default()),
+        1 => Box::new(tetris::Tetris::default()),
+        _ => Box::new(twenty48::TwentyFortyEight::default()),
+    }
+}
+
+/// Draws one board cell, two columns wide, clipped to `area`.
+fn draw_cell(buf: &mut Buffer, area: Rect, x: u16, y: u16, style: Style) {
+    let (column, row) = (area.x + x * 2, area.y + y);
+
+    if column + 1 < area.right() && row < area.bottom() {
+        buf.set_string(column, row, "██", style);
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Arcade {
+    selected: usize,
+    game: Option<Box<dyn Game>>,
+    high_scores: [u32; GAMES.len()],
+    last_tick: Option<Instant>,
+}
+
+impl Arcade {
+    pub fn load() -> Self {
+        let mut arcade = Arcade::default();
+
+        for (name, high_score) in GAMES.iter().zip(arcade.high_scores.iter_mut()) {
+            *high_score = storage::get(&format!("highscore.{name}"))
+                .and_then(|saved| saved.parse().ok())
+                .unwrap_or_default();
+        }
+
+        arcade
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_next(&mut self) {
+        self.selected = (self.selected + 1).min(GAMES.len() - 1);
+    }
+
+    pub fn start(&mut self, now: Instant) {
+        self.game = Some(new_game(self.selected));
+        self.last_tick = Some(now);
+    }
+
+    pub fn stop(&mut self) {
+        self.game = None;
+        self.last_tick = None;
+    }
+
+    pub fn handle(&mut self, action: Action, now: Instant) {
+        match &mut self.game {
+            Some(game) if game.is_over() && action == Action::Select => self.start(now),
+            Some(game) => game.handle(action),
+            None => {}
+        }
+
+        self.record_score();
+    }
+
+    /// Advances the running game as many ticks as it is behind.
+    pub fn tick(&mut self, now: Instant) {
+        let (Some(game), Some(last_tick)) = (&mut self.game, &mut self.last_tick) else {
+            return;
+        };
+
+        let Some(interval) = game.tick_interval() else {
+            return;
+        };
+
+        while !game.is_over() && now.duration_since(*last_tick) >= interval {
+            game.tick();
+            *last_tick += interval;
+        }
+
+        if game.is_over() {
+            *last_tick = now;
+        }
+
+        self.record_score();
+    }
+
+    /// Keeps the clock from catching up on the time spent paused.
+    pub fn resume(&mut self, now: Instant) {
+        if self.game.is_some() {
+            self.last_tick = Some(now);
+        }
+    }
+
+    fn record_score(&mut self) {
+        let Some(game) = &self.game else {
+            return;
+        };
+
+        let high_score = &mut self.high_scores[self.selected];
+        if game.score() > *high_score {
+            *high_score = game.score();
+            storage::set(
+                &format!("highscore.{}", GAMES[self.selected]),
+                &high_score.to_string(),
+            );
+        }
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer) {
+        match &self.game {
+            Some(game) => {
+                let status = if game.is_over() {
+                    format!(
+                        "{} | score {} | best {} | {}, Enter to play again",
+                        GAMES[self.selected],
+                        game.score(),
+                        self.high_scores[self.selected],
+                        if game.is_won() {
+                            "you won"
+                        } else {
+                            "game over"
+                        }
+                    )
+                } else {
+                    format!(
+                        "{} | score {} | best {}",
+                        GAMES[self.selected],
+                        game.score(),
+                        self.high_scores[self.selected]
+                    )
+                };
+
+                Line::from(status).bold().render(area, buf);
+
+                let board = Rect {
+                    y: area.y + 2,
+                    height: area.height.saturating_sub(2),
+                    ..area
+                };
+                game.render(board, buf);
+            }
+            None => {
+                let mut lines = vec![
+                    Line::from(">>> ./games").bold(),
+                    Line::from("Pick something to waste a few minutes on."),
+                    Line::default(),
+                ];
+
+                for (index, (name, high_score)) in
+                    GAMES.iter().zip(self.high_scores.iter()).enumerate()
+                {
+                    let line = Line::from(format!("  {name:<10} best {high_score}"));
+                    lines.push(if index == self.selected {
+                        Line::from(format!("▶ {name:<10} best {high_score}"))
+                            .fg(Color::LightMagenta)
+                    } else {
+                        line
+                    });
+                }
+
+                Paragraph::new(lines).render(area, buf);
+            }
+        }
+    }
+}
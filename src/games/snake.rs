@@ -0,0 +1,178 @@
+use std::collections::VecDeque;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Widget},
+};
+use web_time::Duration;
+
+use super::{draw_cell, Game};
+use crate::{keymap::Action, random::Rng};
+
+const WIDTH: i16 = 24;
+const HEIGHT: i16 = 16;
+
+#[derive(Debug)]
+pub struct Snake {
+    body: VecDeque<(i16, i16)>,
+    direction: (i16, i16),
+    next_direction: (i16, i16),
+    food: (i16, i16),
+    score: u32,
+    over: bool,
+    won: bool,
+    rng: Rng,
+}
+
+impl Default for Snake {
+    fn default() -> Self {
+        let mut snake = Self {
+            body: VecDeque::from([(WIDTH / 2, HEIGHT / 2), (WIDTH / 2 - 1, HEIGHT / 2)]),
+            direction: (1, 0),
+            next_direction: (1, 0),
+            food: (0, 0),
+            score: 0,
+            over: false,
+            won: false,
+            rng: Rng::default(),
+        };
+        snake.place_food();
+        snake
+    }
+}
+
+impl Snake {
+    /// Puts the food on a random free cell. A snake filling the whole
+    /// board leaves none, and has won.
+    fn place_food(&mut self) {
+        let free: Vec<(i16, i16)> = (0..HEIGHT)
+            .flat_map(|y| (0..WIDTH).map(move |x| (x, y)))
+            .filter(|cell| !self.body.contains(cell))
+            .collect();
+
+        if free.is_empty() {
+            self.over = true;
+            self.won = true;
+            return;
+        }
+
+        self.food = free[self.rng.below(free.len())];
+    }
+}
+
+impl Game for Snake {
+    fn tick_interval(&self) -> Option<Duration> {
+        Some(Duration::from_millis(
+            140u64.saturating_sub(3 * u64::from(self.score)).max(60),
+        ))
+    }
+
+    fn tick(&mut self) {
+        self.direction = self.next_direction;
+
+        let (x, y) = self.body[0];
+        let head = (x + self.direction.0, y + self.direction.1);
+
+        let hits_wall = !(0..WIDTH).contains(&head.0) || !(0..HEIGHT).contains(&head.1);
+        if hits_wall || self.body.contains(&head) {
+            self.over = true;
+            return;
+        }
+
+        self.body.push_front(head);
+
+        if head == self.food {
+            self.score += 1;
+            self.place_food();
+        } else {
+            self.body.pop_back();
+        }
+    }
+
+    fn handle(&mut self, action: Action) {
+        let direction = match action {
+            Action::MoveUp => (0, -1),
+            Action::MoveDown => (0, 1),
+            Action::MoveLeft => (-1, 0),
+            Action::MoveRight => (1, 0),
+            _ => return,
+        };
+
+        // Turning back into the neck would end the game on the spot.
+        if direction != (-self.direction.0, -self.direction.1) {
+            self.next_direction = direction;
+        }
+    }
+
+    fn score(&self) -> u32 {
+        self.score
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn is_won(&self) -> bool {
+        self.won
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let board =
+            Rect::new(area.x, area.y, WIDTH as u16 * 2 + 2, HEIGHT as u16 + 2).intersection(area);
+        let block = Block::bordered().border_style(Style::default().fg(Color::LightMagenta));
+        let inner = block.inner(board);
+        block.render(board, buf);
+
+        draw_cell(
+            buf,
+            inner,
+            self.food.0 as u16,
+            self.food.1 as u16,
+            Style::default().fg(Color::LightRed),
+        );
+
+        for (index, (x, y)) in self.body.iter().enumerate() {
+            let color = if index == 0 {
+                Color::LightGreen
+            } else {
+                Color::Green
+            };
+            draw_cell(buf, inner, *x as u16, *y as u16, Style::default().fg(color));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filling_the_board_wins() {
+        let mut snake = Snake {
+            body: (0..HEIGHT)
+                .flat_map(|y| (0..WIDTH).map(move |x| (x, y)))
+                .collect(),
+            ..Snake::default()
+        };
+
+        snake.place_food();
+        assert!(snake.is_over() && snake.is_won());
+    }
+
+    #[test]
+    fn food_lands_on_the_last_free_cell() {
+        let mut snake = Snake {
+            body: (0..HEIGHT)
+                .flat_map(|y| (0..WIDTH).map(move |x| (x, y)))
+                .filter(|cell| *cell != (3, 5))
+                .collect(),
+            ..Snake::default()
+        };
+
+        snake.place_food();
+        assert_eq!(snake.food, (3, 5));
+        assert!(!snake.is_over());
+    }
+}
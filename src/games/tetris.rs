@@ -0,0 +1,347 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Widget},
+};
+use web_time::Duration;
+
+use super::{draw_cell, Game};
+use crate::{keymap::Action, random::Rng};
+
+const WIDTH: usize = 10;
+const HEIGHT: usize = 20;
+
+/// A tetromino in its spawn rotation, within a square box of `size`.
+struct Shape {
+    size: usize,
+    cells: [(usize, usize); 4],
+    color: Color,
+}
+
+const fn shape(size: usize, cells: [(usize, usize); 4], color: Color) -> Shape {
+    Shape { size, cells, color }
+}
+
+const PIECES: [Shape; 7] = [
+    shape(4, [(0, 1), (1, 1), (2, 1), (3, 1)], Color::Cyan),
+    shape(2, [(0, 0), (1, 0), (0, 1), (1, 1)], Color::Yellow),
+    shape(3, [(1, 0), (0, 1), (1, 1), (2, 1)], Color::Magenta),
+    shape(3, [(1, 0), (2, 0), (0, 1), (1, 1)], Color::Green),
+    shape(3, [(0, 0), (1, 0), (1, 1), (2, 1)], Color::Red),
+    shape(3, [(0, 0), (0, 1), (1, 1), (2, 1)], Color::Blue),
+    shape(3, [(2, 0), (0, 1), (1, 1), (2, 1)], Color::LightRed),
+];
+
+const LINE_SCORES: [u32; 5] = [0, 100, 300, 500, 800];
+
+#[derive(Debug, Clone, Copy)]
+struct Piece {
+    kind: usize,
+    rotation: usize,
+    x: i16,
+    y: i16,
+}
+
+impl Piece {
+    fn new(kind: usize) -> Self {
+        Self {
+            kind,
+            rotation: 0,
+            x: (WIDTH as i16 - PIECES[kind].size as i16) / 2,
+            y: 0,
+        }
+    }
+
+    fn cells(self) -> impl Iterator<Item = (i16, i16)> {
+        let Shape { size, cells, .. } = PIECES[self.kind];
+
+        cells.into_iter().map(move |(mut x, mut y)| {
+            for _ in 0..self.rotation {
+                (x, y) = (size - 1 - y, x);
+            }
+            (self.x + x as i16, self.y + y as i16)
+        })
+    }
+
+    fn color(self) -> Color {
+        PIECES[self.kind].color
+    }
+}
+
+#[derive(Debug)]
+pub struct Tetris {
+    board: [[Option<Color>; WIDTH]; HEIGHT],
+    piece: Piece,
+    next: usize,
+    lines: u32,
+    score: u32,
+    over: bool,
+    rng: Rng,
+}
+
+impl Default for Tetris {
+    fn default() -> Self {
+        let mut rng = Rng::default();
+
+        Self {
+            board: [[None; WIDTH]; HEIGHT],
+            piece: Piece::new(rng.below(PIECES.len())),
+            next: rng.below(PIECES.len()),
+            lines: 0,
+            score: 0,
+            over: false,
+            rng,
+        }
+    }
+}
+
+impl Tetris {
+    fn fits(&self, piece: Piece) -> bool {
+        piece.cells().all(|(x, y)| {
+            (0..WIDTH as i16).contains(&x)
+                && (0..HEIGHT as i16).contains(&y)
+                && self.board[y as usize][x as usize].is_none()
+        })
+    }
+
+    /// Moves the piece if it fits there, returning whether it did.
+    fn shift(&mut self, x: i16, y: i16) -> bool {
+        let moved = Piece {
+            x: self.piece.x + x,
+            y: self.piece.y + y,
+            ..self.piece
+        };
+
+        let fits = self.fits(moved);
+        if fits {
+            self.piece = moved;
+        }
+        fits
+    }
+
+    fn rotate(&mut self) {
+        let rotated = Piece {
+            rotation: (self.piece.rotation + 1) % 4,
+            ..self.piece
+        };
+
+        // A tiny wall kick, so pieces can still turn next to the walls.
+        for kick in [0, -1, 1, -2, 2] {
+            let kicked = Piece {
+                x: rotated.x + kick,
+                ..rotated
+            };
+
+            if self.fits(kicked) {
+                self.piece = kicked;
+                return;
+            }
+        }
+    }
+
+    fn lock(&mut self) {
+        for (x, y) in self.piece.cells() {
+            self.board[y as usize][x as usize] = Some(self.piece.color());
+        }
+
+        let remaining: Vec<_> = self
+            .board
+            .iter()
+            .filter(|row| row.iter().any(Option::is_none))
+            .copied()
+            .collect();
+        let cleared = HEIGHT - remaining.len();
+
+        self.board = [[None; WIDTH]; HEIGHT];
+        self.board[cleared..].copy_from_slice(&remaining);
+
+        self.lines += cleared as u32;
+        self.score += LINE_SCORES[cleared] * (self.level() + 1);
+
+        self.piece = Piece::new(self.next);
+        self.next = self.rng.below(PIECES.len());
+        self.over = !self.fits(self.piece);
+    }
+
+    fn level(&self) -> u32 {
+        self.lines / 10
+    }
+}
+
+impl Game for Tetris {
+    fn tick_interval(&self) -> Option<Duration> {
+        Some(Duration::from_millis(
+            500u64.saturating_sub(40 * u64::from(self.level())).max(100),
+        ))
+    }
+
+    fn tick(&mut self) {
+        if !self.shift(0, 1) {
+            self.lock();
+        }
+    }
+
+    fn handle(&mut self, action: Action) {
+        if self.over {
+            return;
+        }
+
+        match action {
+            Action::MoveLeft => {
+                self.shift(-1, 0);
+            }
+            Action::MoveRight => {
+                self.shift(1, 0);
+            }
+            Action::MoveUp => self.rotate(),
+            Action::MoveDown => self.tick(),
+            Action::Drop => {
+                while self.shift(0, 1) {
+                    self.score += 2;
+                }
+                self.lock();
+            }
+            _ => {}
+        }
+    }
+
+    fn score(&self) -> u32 {
+        self.score
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let board =
+            Rect::new(area.x, area.y, WIDTH as u16 * 2 + 2, HEIGHT as u16 + 2).intersection(area);
+        let block = Block::bordered().border_style(Style::default().fg(Color::LightMagenta));
+        let inner = block.inner(board);
+        block.render(board, buf);
+
+        for (y, row) in self.board.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                if let Some(color) = cell {
+                    draw_cell(buf, inner, x as u16, y as u16, Style::default().fg(*color));
+                }
+            }
+        }
+
+        if !self.over {
+            for (x, y) in self.piece.cells() {
+                let style = Style::default().fg(self.piece.color());
+                draw_cell(buf, inner, x as u16, y as u16, style);
+            }
+        }
+
+        let side = Rect {
+            x: board.right() + 2,
+            width: area.right().saturating_sub(board.right() + 2),
+            ..area
+        };
+
+        if side.width >= 10 && side.height >= 8 {
+            buf.set_string(side.x, side.y, "next", Style::default());
+            for (x, y) in Piece::new(self.next).cells() {
+                let x = (x - Piece::new(self.next).x) as u16;
+                let style = Style::default().fg(PIECES[self.next].color);
+                let preview = Rect {
+                    y: side.y + 2,
+                    height: side.height.saturating_sub(2),
+                    ..side
+                };
+                draw_cell(buf, preview, x, y as u16, style);
+            }
+
+            buf.set_string(
+                side.x,
+                side.y + 6,
+                format!("lines {}", self.lines),
+                Style::default(),
+            );
+            buf.set_string(
+                side.x,
+                side.y + 7,
+                format!("level {}", self.level()),
+                Style::default(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(piece: Piece) -> Vec<(i16, i16)> {
+        let mut cells: Vec<_> = piece.cells().collect();
+        cells.sort();
+        cells
+    }
+
+    #[test]
+    fn four_turns_come_back_around() {
+        let mut tetris = Tetris {
+            piece: Piece {
+                y: 5,
+                ..Piece::new(0)
+            },
+            ..Tetris::default()
+        };
+        let start = cells(tetris.piece);
+
+        tetris.rotate();
+        assert_eq!(cells(tetris.piece), [(5, 5), (5, 6), (5, 7), (5, 8)]);
+
+        for _ in 0..3 {
+            tetris.rotate();
+        }
+        assert_eq!(cells(tetris.piece), start);
+    }
+
+    #[test]
+    fn turns_next_to_a_wall_kick_off_it() {
+        let mut tetris = Tetris {
+            piece: Piece {
+                rotation: 1,
+                x: -2,
+                y: 5,
+                ..Piece::new(0)
+            },
+            ..Tetris::default()
+        };
+        assert!(tetris.fits(tetris.piece));
+
+        tetris.rotate();
+        assert_eq!(tetris.piece.rotation, 2);
+        assert!(tetris.fits(tetris.piece));
+        assert!(cells(tetris.piece).iter().all(|(x, _)| *x >= 0));
+    }
+
+    #[test]
+    fn full_rows_are_cleared_and_scored() {
+        let mut tetris = Tetris {
+            piece: Piece {
+                x: 0,
+                y: HEIGHT as i16 - 2,
+                ..Piece::new(0)
+            },
+            ..Tetris::default()
+        };
+        tetris.board[HEIGHT - 1] = [Some(Color::Gray); WIDTH];
+        tetris.board[HEIGHT - 1][..4].fill(None);
+        tetris.board[HEIGHT - 2][9] = Some(Color::Gray);
+
+        tetris.lock();
+
+        assert_eq!((tetris.lines, tetris.score), (1, 100));
+        assert_eq!(tetris.board[HEIGHT - 1][9], Some(Color::Gray));
+        assert_eq!(tetris.board[HEIGHT - 1][..9], [None; 9]);
+        assert!(tetris.board[..HEIGHT - 1]
+            .iter()
+            .flatten()
+            .all(Option::is_none));
+    }
+}
@@ -0,0 +1,231 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Color, Style, Stylize},
+    widgets::{Block, Paragraph, Widget},
+};
+use web_time::Duration;
+
+use super::Game;
+use crate::{keymap::Action, random::Rng};
+
+const SIZE: usize = 4;
+const TILE_WIDTH: u16 = 8;
+const TILE_HEIGHT: u16 = 3;
+
+#[derive(Debug)]
+pub struct TwentyFortyEight {
+    board: [[u32; SIZE]; SIZE],
+    score: u32,
+    over: bool,
+    rng: Rng,
+}
+
+impl Default for TwentyFortyEight {
+    fn default() -> Self {
+        let mut game = Self {
+            board: [[0; SIZE]; SIZE],
+            score: 0,
+            over: false,
+            rng: Rng::default(),
+        };
+        game.spawn();
+        game.spawn();
+        game
+    }
+}
+
+impl TwentyFortyEight {
+    fn spawn(&mut self) {
+        let empty: Vec<(usize, usize)> = (0..SIZE)
+            .flat_map(|y| (0..SIZE).map(move |x| (x, y)))
+            .filter(|(x, y)| self.board[*y][*x] == 0)
+            .collect();
+
+        if empty.is_empty() {
+            return;
+        }
+
+        let (x, y) = empty[self.rng.below(empty.len())];
+        self.board[y][x] = if self.rng.below(10) == 0 { 4 } else { 2 };
+    }
+
+    /// Slides and merges a single row towards its start.
+    fn slide(row: [u32; SIZE]) -> ([u32; SIZE], u32) {
+        let tiles: Vec<u32> = row.into_iter().filter(|tile| *tile != 0).collect();
+        let mut slid = [0; SIZE];
+        let mut score = 0;
+        let (mut read, mut write) = (0, 0);
+
+        while read < tiles.len() {
+            if read + 1 < tiles.len() && tiles[read] == tiles[read + 1] {
+                slid[write] = tiles[read] * 2;
+                score += slid[write];
+                read += 2;
+            } else {
+                slid[write] = tiles[read];
+                read += 1;
+            }
+            write += 1;
+        }
+
+        (slid, score)
+    }
+
+    /// Reads the board as rows slid towards `direction`.
+    fn line(&self, direction: Action, index: usize) -> [u32; SIZE] {
+        std::array::from_fn(|step| {
+            let (x, y) = TwentyFortyEight::position(direction, index, step);
+            self.board[y][x]
+        })
+    }
+
+    fn position(direction: Action, index: usize, step: usize) -> (usize, usize) {
+        match direction {
+            Action::MoveLeft => (step, index),
+            Action::MoveRight => (SIZE - 1 - step, index),
+            Action::MoveUp => (index, step),
+            _ => (index, SIZE - 1 - step),
+        }
+    }
+
+    fn can_move(&self) -> bool {
+        (0..SIZE).any(|y| {
+            (0..SIZE).any(|x| {
+                self.board[y][x] == 0
+                    || (x + 1 < SIZE && self.board[y][x] == self.board[y][x + 1])
+                    || (y + 1 < SIZE && self.board[y][x] == self.board[y + 1][x])
+            })
+        })
+    }
+
+    fn color(tile: u32) -> Color {
+        match tile {
+            0 => Color::DarkGray,
+            2 | 4 => Color::Rgb(139, 233, 253),
+            8 | 16 => Color::Rgb(255, 184, 108),
+            32 | 64 => Color::Rgb(255, 121, 198),
+            128 | 256 => Color::LightYellow,
+            512 | 1024 => Color::LightGreen,
+            _ => Color::LightRed,
+        }
+    }
+}
+
+impl Game for TwentyFortyEight {
+    fn tick_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    fn tick(&mut self) {}
+
+    fn handle(&mut self, action: Action) {
+        if self.over
+            || !matches!(
+                action,
+                Action::MoveUp | Action::MoveDown | Action::MoveLeft | Action::MoveRight
+            )
+        {
+            return;
+        }
+
+        let mut moved = false;
+
+        for index in 0..SIZE {
+            let (slid, score) = TwentyFortyEight::slide(self.line(action, index));
+            self.score += score;
+
+            for (step, tile) in slid.into_iter().enumerate() {
+                let (x, y) = TwentyFortyEight::position(action, index, step);
+                moved |= self.board[y][x] != tile;
+                self.board[y][x] = tile;
+            }
+        }
+
+        if moved {
+            self.spawn();
+        }
+
+        self.over = !self.can_move();
+    }
+
+    fn score(&self) -> u32 {
+        self.score
+    }
+
+    fn is_over(&self) -> bool {
+        self.over
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        for (y, row) in self.board.iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                let tile_area = Rect::new(
+                    area.x + x as u16 * TILE_WIDTH,
+                    area.y + y as u16 * TILE_HEIGHT,
+                    TILE_WIDTH,
+                    TILE_HEIGHT,
+                )
+                .intersection(area);
+
+                let label = if *tile == 0 {
+                    String::new()
+                } else {
+                    tile.to_string()
+                };
+
+                Paragraph::new(label)
+                    .alignment(Alignment::Center)
+                    .bold()
+                    .block(
+                        Block::bordered()
+                            .border_style(Style::default().fg(TwentyFortyEight::color(*tile))),
+                    )
+                    .fg(TwentyFortyEight::color(*tile))
+                    .render(tile_area, buf);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tiles_merge_once_per_slide() {
+        assert_eq!(TwentyFortyEight::slide([2, 2, 2, 2]), ([4, 4, 0, 0], 8));
+        assert_eq!(TwentyFortyEight::slide([4, 4, 8, 0]), ([8, 8, 0, 0], 8));
+        assert_eq!(TwentyFortyEight::slide([2, 0, 2, 4]), ([4, 4, 0, 0], 4));
+        assert_eq!(TwentyFortyEight::slide([2, 4, 2, 4]), ([2, 4, 2, 4], 0));
+    }
+
+    #[test]
+    fn moves_slide_every_row_and_add_up_the_score() {
+        let mut game = TwentyFortyEight {
+            board: [[2, 2, 0, 0], [0, 0, 4, 4], [8, 0, 0, 8], [2, 4, 8, 16]],
+            ..TwentyFortyEight::default()
+        };
+
+        game.handle(Action::MoveRight);
+
+        assert_eq!(game.score, 4 + 8 + 16);
+        assert_eq!(game.board[0][SIZE - 1], 4);
+        assert_eq!(game.board[1][SIZE - 1], 8);
+        assert_eq!(game.board[2][SIZE - 1], 16);
+        assert_eq!(game.board[3], [2, 4, 8, 16]);
+    }
+
+    #[test]
+    fn a_locked_board_is_over() {
+        let mut game = TwentyFortyEight {
+            board: [[2, 4, 2, 4], [4, 2, 4, 2], [2, 4, 2, 4], [4, 2, 4, 2]],
+            ..TwentyFortyEight::default()
+        };
+
+        game.handle(Action::MoveLeft);
+
+        assert!(game.is_over());
+        assert_eq!(game.score, 0);
+    }
+}
@@ -0,0 +1,172 @@
+//! The `(gdb)` prompt of the about page.
+//!
+//! The about page is a debugging session on the core `./tarbetu` dumped.
+//! Visitors type commands at the prompt and every answer is a section of
+//! the page, so the transcript grows into the page as they explore it.
+
+use ratzilla::event::{KeyCode, KeyEvent};
+
+use crate::{focus::Reply, shell, text};
+
+const PROMPT: &str = "(gdb) ";
+const CURSOR: char = '█';
+
+#[derive(Debug)]
+pub struct Gdb {
+    transcript: String,
+    input: String,
+    history: Vec<String>,
+    /// The history entry shown in the input while browsing with the arrows.
+    recalled: Option<usize>,
+    exited: bool,
+    /// Whether the last command went to the shell, with `shell` or `!`.
+    shelled_out: bool,
+}
+
+impl Default for Gdb {
+    fn default() -> Self {
+        let mut gdb = Self {
+            transcript: text::ABOUT.to_string(),
+            input: String::new(),
+            history: Vec::new(),
+            recalled: None,
+            exited: false,
+            shelled_out: false,
+        };
+
+        gdb.run("bt", false);
+        gdb.history.clear();
+        gdb
+    }
+}
+
+impl Gdb {
+    /// Handles a key typed into the prompt. `sudo` tells whether the
+    /// visitor found the secret that lets the shell read root's files.
+    pub fn handle(&mut self, event: &KeyEvent, sudo: bool) -> Reply {
+        if event.ctrl || event.alt {
+            return Reply::Ignored;
+        }
+
+        if self.exited {
+            if !matches!(event.code, KeyCode::Char(_)) {
+                return Reply::Ignored;
+            }
+            *self = Gdb::default();
+        }
+
+        match event.code {
+            KeyCode::Char(letter) => {
+                self.input.push(letter);
+                self.recalled = None;
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Up if !self.history.is_empty() => {
+                let index = self
+                    .recalled
+                    .map_or(self.history.len() - 1, |index| index.saturating_sub(1));
+                self.recalled = Some(index);
+                self.input = self.history[index].clone();
+            }
+            KeyCode::Down => match self.recalled {
+                Some(index) if index + 1 < self.history.len() => {
+                    self.recalled = Some(index + 1);
+                    self.input = self.history[index + 1].clone();
+                }
+                Some(_) => {
+                    self.recalled = None;
+                    self.input.clear();
+                }
+                None => return Reply::Ignored,
+            },
+            KeyCode::Enter => {
+                // An empty line repeats the last command, as in gdb.
+                let input = std::mem::take(&mut self.input);
+                let command = match input.trim() {
+                    "" => self.history.last().cloned().unwrap_or_default(),
+                    command => command.to_string(),
+                };
+                self.recalled = None;
+
+                if !command.is_empty() && self.history.last() != Some(&command) {
+                    self.history.push(command.clone());
+                }
+
+                if command.is_empty() {
+                    self.run("", sudo);
+                    return Reply::Consumed;
+                }
+
+                if self.run(&command, sudo) {
+                    self.transcript
+                        .push_str(&format!("\n{PROMPT}{command}\n{}", text::GDB_QUIT));
+                    self.exited = true;
+                    return Reply::Quit;
+                }
+                return Reply::Ran;
+            }
+            _ => return Reply::Ignored,
+        }
+
+        Reply::Consumed
+    }
+
+    /// Whether the command just run was a shell command.
+    pub fn shelled_out(&self) -> bool {
+        self.shelled_out
+    }
+
+    /// The session so far, followed by the prompt and what is being typed.
+    pub fn text(&self) -> String {
+        if self.exited {
+            return self.transcript.clone();
+        }
+
+        format!("{}\n{PROMPT}{}{CURSOR}", self.transcript, self.input)
+    }
+
+    /// Runs a command and records it with its output. Returns whether the
+    /// command quits gdb.
+    fn run(&mut self, command: &str, sudo: bool) -> bool {
+        let mut words = command.split_whitespace();
+        let name = words.next().unwrap_or_default();
+        let argument = words.collect::<Vec<_>>().join(" ");
+        self.shelled_out = name == "shell" || name.starts_with('!');
+
+        let output = match (name, argument.as_str()) {
+            ("", _) => String::new(),
+            ("shell", line) => shell::run(line, sudo),
+            (bang, line) if bang.starts_with('!') => {
+                shell::run(&format!("{} {line}", &bang[1..]), sudo)
+            }
+            ("bt" | "backtrace" | "where", _) => text::GDB_BACKTRACE.to_string(),
+            ("info" | "i", "") => {
+                r#""info" must be followed by the name of an info command."#.to_string()
+            }
+            ("info" | "i", "registers" | "reg" | "r") => text::GDB_REGISTERS.to_string(),
+            ("info" | "i", "links") => text::GDB_LINKS.to_string(),
+            ("info" | "i", other) => {
+                format!(r#"Undefined info command: "{other}".  Try "help info"."#)
+            }
+            ("print" | "p", "motivation") => text::GDB_MOTIVATION.to_string(),
+            ("print" | "p", "") => "$1 = <error: The history is empty.>".to_string(),
+            ("print" | "p", other) => format!(r#"No symbol "{other}" in current context."#),
+            ("list" | "l", _) => text::GDB_LIST.to_string(),
+            ("frame" | "f", "" | "0") => text::GDB_FRAME.to_string(),
+            ("frame" | "f", level) => format!("No frame at level {level}."),
+            ("help" | "h", _) => text::GDB_HELP.to_string(),
+            ("quit" | "q", _) => return true,
+            (other, _) => format!(r#"Undefined command: "{other}".  Try "help"."#),
+        };
+
+        self.transcript.push_str(&format!("\n{PROMPT}{command}"));
+        if !output.is_empty() {
+            self.transcript.push('\n');
+            self.transcript.push_str(&output);
+        }
+
+        false
+    }
+}
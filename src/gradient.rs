@@ -0,0 +1,176 @@
+//! Per-character color effects.
+//!
+//! A [`Gradient`] recolors every visible character of a [`Text`] or
+//! [`Line`] by its position, along a direction and either through color
+//! stops or around the hue wheel. Shifting the phase with the clock makes
+//! the colors cycle. Colors come from a small lookup table built once per
+//! call, so painting a full intro frame every frame stays cheap.
+
+use ratatui::{
+    style::Color,
+    text::{Line, Span, Text},
+};
+use web_time::Duration;
+
+/// How many distinct colors a gradient is quantized to.
+const STEPS: usize = 64;
+
+pub const OCEAN: &[[u8; 3]] = &[[0, 255, 255], [0, 120, 255], [150, 220, 255]];
+pub const SUNSET: &[[u8; 3]] = &[[255, 230, 0], [255, 140, 0], [255, 250, 160]];
+pub const EMBER: &[[u8; 3]] = &[[255, 40, 40], [180, 0, 90], [255, 120, 80]];
+pub const NEON: &[[u8; 3]] = &[[255, 80, 255], [80, 220, 255]];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Horizontal,
+    Vertical,
+    /// Top left to bottom right. Rows count double, since cells are about
+    /// twice as tall as wide.
+    Diagonal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Palette {
+    /// Evenly spaced RGB stops, wrapping from the last back to the first.
+    Stops(&'static [[u8; 3]]),
+    Rainbow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gradient {
+    palette: Palette,
+    direction: Direction,
+    /// Offset along the gradient, in whole turns.
+    phase: f32,
+}
+
+impl Gradient {
+    pub const fn new(stops: &'static [[u8; 3]], direction: Direction) -> Self {
+        Self {
+            palette: Palette::Stops(stops),
+            direction,
+            phase: 0.0,
+        }
+    }
+
+    pub const fn rainbow(direction: Direction) -> Self {
+        Self {
+            palette: Palette::Rainbow,
+            direction,
+            phase: 0.0,
+        }
+    }
+
+    /// Shifts the colors by how far `elapsed` is into a cycle of `period`.
+    pub fn cycle(mut self, elapsed: Duration, period: Duration) -> Self {
+        self.phase = (elapsed.as_secs_f32() / period.as_secs_f32()).fract();
+        self
+    }
+
+    pub fn paint_line<'a>(&self, line: Line<'a>) -> Line<'a> {
+        let table = self.table();
+        let width = line.width();
+
+        self.paint(&table, line, 0, width, 1)
+    }
+
+    pub fn paint_text<'a>(&self, text: Text<'a>) -> Text<'a> {
+        let table = self.table();
+        let width = text.width();
+        let height = text.height();
+
+        let lines = text
+            .lines
+            .into_iter()
+            .enumerate()
+            .map(|(row, line)| self.paint(&table, line, row, width, height))
+            .collect::<Vec<_>>();
+
+        Text::from(lines)
+    }
+
+    fn paint<'a>(
+        &self,
+        table: &[Color; STEPS],
+        line: Line<'a>,
+        row: usize,
+        width: usize,
+        height: usize,
+    ) -> Line<'a> {
+        let mut spans: Vec<Span<'a>> = Vec::with_capacity(line.spans.len());
+        let mut column = 0;
+
+        for span in line.spans {
+            let base = span.style;
+
+            for letter in span.content.chars() {
+                let style = if letter.is_whitespace() {
+                    base
+                } else {
+                    base.fg(table[self.step(column, row, width, height)])
+                };
+
+                match spans.last_mut() {
+                    Some(last) if last.style == style => last.content.to_mut().push(letter),
+                    _ => spans.push(Span::styled(String::from(letter), style)),
+                }
+                column += 1;
+            }
+        }
+
+        Line { spans, ..line }
+    }
+
+    /// Where a cell falls along the gradient, as an index into the table.
+    fn step(&self, column: usize, row: usize, width: usize, height: usize) -> usize {
+        let (position, length) = match self.direction {
+            Direction::Horizontal => (column, width),
+            Direction::Vertical => (row, height),
+            Direction::Diagonal => (column + row * 2, width + height * 2),
+        };
+
+        position * STEPS / length.max(1) % STEPS
+    }
+
+    /// The colors of one turn around the gradient, phase applied.
+    fn table(&self) -> [Color; STEPS] {
+        std::array::from_fn(|step| {
+            let turn = (step as f32 / STEPS as f32 + self.phase).fract();
+
+            match self.palette {
+                Palette::Rainbow => hue(turn),
+                Palette::Stops([]) => Color::Reset,
+                Palette::Stops(stops) => {
+                    let position = turn * stops.len() as f32;
+                    let from = stops[position as usize % stops.len()];
+                    let to = stops[(position as usize + 1) % stops.len()];
+                    let mix = position.fract();
+
+                    let channel = |index: usize| {
+                        let from = f32::from(from[index]);
+                        let to = f32::from(to[index]);
+                        (from + (to - from) * mix) as u8
+                    };
+
+                    Color::Rgb(channel(0), channel(1), channel(2))
+                }
+            }
+        })
+    }
+}
+
+/// A fully saturated color at `turn` around the hue wheel.
+fn hue(turn: f32) -> Color {
+    let sector = turn * 6.0;
+    let rising = (sector.fract() * 255.0) as u8;
+    let falling = 255 - rising;
+
+    match sector as usize % 6 {
+        0 => Color::Rgb(255, rising, 0),
+        1 => Color::Rgb(falling, 255, 0),
+        2 => Color::Rgb(0, 255, rising),
+        3 => Color::Rgb(0, falling, 255),
+        4 => Color::Rgb(rising, 0, 255),
+        _ => Color::Rgb(255, 0, falling),
+    }
+}
@@ -0,0 +1,454 @@
+//! Syntax highlighting for fenced code blocks.
+//!
+//! Each language is a small [`Grammar`] table: keywords, types, literals
+//! and the shape of its comments and strings. The tokenizer is shared and
+//! line based, carrying only the block comment state between lines, which
+//! is plenty for the short samples the pages show.
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+#[derive(Debug)]
+pub struct Grammar {
+    pub name: &'static str,
+    aliases: &'static [&'static str],
+    keywords: &'static [&'static str],
+    types: &'static [&'static str],
+    literals: &'static [&'static str],
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+    /// Elixir's `:atom` and `@attribute`.
+    atoms: bool,
+    /// The shell's `$variable`.
+    variables: bool,
+    /// Rust's `name!` and C++'s `#directive`.
+    macros: bool,
+}
+
+const RUST: Grammar = Grammar {
+    name: "rust",
+    aliases: &["rs"],
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "extern", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+        "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "type",
+        "unsafe", "use", "where", "while",
+    ],
+    types: &[
+        "bool", "char", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "str", "u8",
+        "u16", "u32", "u64", "u128", "usize", "String", "Vec", "Option", "Result", "Box", "Rc",
+        "RefCell",
+    ],
+    literals: &["true", "false", "None", "Some", "Ok", "Err"],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"'],
+    atoms: false,
+    variables: false,
+    macros: true,
+};
+
+const ELIXIR: Grammar = Grammar {
+    name: "elixir",
+    aliases: &["ex", "exs", "iex"],
+    keywords: &[
+        "after",
+        "alias",
+        "case",
+        "catch",
+        "cond",
+        "def",
+        "defp",
+        "defmodule",
+        "defstruct",
+        "defmacro",
+        "defimpl",
+        "defprotocol",
+        "do",
+        "else",
+        "end",
+        "fn",
+        "for",
+        "if",
+        "import",
+        "in",
+        "quote",
+        "raise",
+        "receive",
+        "require",
+        "rescue",
+        "try",
+        "unless",
+        "unquote",
+        "use",
+        "when",
+        "with",
+    ],
+    types: &[
+        "Enum",
+        "GenServer",
+        "Kernel",
+        "List",
+        "Map",
+        "Process",
+        "String",
+        "Supervisor",
+        "Task",
+    ],
+    literals: &["true", "false", "nil"],
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: &['"', '\''],
+    atoms: true,
+    variables: false,
+    macros: false,
+};
+
+const CPP: Grammar = Grammar {
+    name: "c++",
+    aliases: &["cpp", "cxx", "hpp", "c"],
+    keywords: &[
+        "auto",
+        "break",
+        "case",
+        "class",
+        "const",
+        "constexpr",
+        "continue",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "enum",
+        "explicit",
+        "for",
+        "friend",
+        "if",
+        "inline",
+        "namespace",
+        "new",
+        "operator",
+        "private",
+        "protected",
+        "public",
+        "return",
+        "sizeof",
+        "static",
+        "struct",
+        "switch",
+        "template",
+        "this",
+        "typename",
+        "using",
+        "virtual",
+        "while",
+    ],
+    types: &[
+        "bool",
+        "char",
+        "double",
+        "float",
+        "int",
+        "long",
+        "short",
+        "signed",
+        "unsigned",
+        "void",
+        "size_t",
+        "std",
+        "string",
+        "vector",
+        "unique_ptr",
+        "shared_ptr",
+    ],
+    literals: &["true", "false", "nullptr", "NULL"],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\''],
+    atoms: false,
+    variables: false,
+    macros: true,
+};
+
+const SHELL: Grammar = Grammar {
+    name: "shell",
+    aliases: &["sh", "bash", "zsh", "console"],
+    keywords: &[
+        "case", "do", "done", "elif", "else", "esac", "exit", "export", "fi", "for", "function",
+        "if", "in", "local", "return", "then", "until", "while",
+    ],
+    types: &[
+        "cargo", "cat", "cd", "echo", "gdb", "git", "ls", "make", "mix", "rpmbuild", "sudo",
+    ],
+    literals: &["true", "false"],
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: &['"', '\''],
+    atoms: false,
+    variables: true,
+    macros: false,
+};
+
+const LOX: Grammar = Grammar {
+    name: "lox",
+    aliases: &["tarlox"],
+    keywords: &[
+        "and", "class", "else", "for", "fun", "if", "or", "print", "return", "super", "this",
+        "var", "while",
+    ],
+    types: &["clock"],
+    literals: &["true", "false", "nil"],
+    line_comments: &["//"],
+    block_comment: None,
+    quotes: &['"'],
+    atoms: false,
+    variables: false,
+    macros: false,
+};
+
+pub const GRAMMARS: &[Grammar] = &[RUST, ELIXIR, CPP, SHELL, LOX];
+
+pub fn grammar(tag: &str) -> Option<&'static Grammar> {
+    let tag = tag.to_lowercase();
+
+    GRAMMARS
+        .iter()
+        .find(|grammar| grammar.name == tag || grammar.aliases.contains(&tag.as_str()))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Plain,
+    Keyword,
+    Type,
+    Literal,
+    Number,
+    String,
+    Comment,
+    Atom,
+    Function,
+    Macro,
+}
+
+impl Token {
+    fn style(self) -> Style {
+        match self {
+            Token::Plain => Style::new().fg(Color::Gray),
+            Token::Keyword => Style::new().fg(Color::LightMagenta),
+            Token::Type => Style::new().fg(Color::LightYellow),
+            Token::Literal | Token::Number => Style::new().fg(Color::LightRed),
+            Token::String => Style::new().fg(Color::LightGreen),
+            Token::Comment => Style::new()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::ITALIC),
+            Token::Atom => Style::new().fg(Color::LightCyan),
+            Token::Function => Style::new().fg(Color::LightBlue),
+            Token::Macro => Style::new().fg(Color::Cyan),
+        }
+    }
+}
+
+/// Highlights `lines` with `grammar`, or leaves them plain without one.
+pub fn highlight(grammar: Option<&Grammar>, lines: &[&str]) -> Vec<Line<'static>> {
+    let mut in_comment = false;
+
+    lines
+        .iter()
+        .map(|line| match grammar {
+            Some(grammar) => grammar.line(line, &mut in_comment),
+            None => Line::styled(line.to_string(), Token::Plain.style()),
+        })
+        .collect()
+}
+
+impl Grammar {
+    fn line(&self, line: &str, in_comment: &mut bool) -> Line<'static> {
+        let chars: Vec<char> = line.chars().collect();
+        let rest = |index: usize| chars[index..].iter().collect::<String>();
+        let mut spans = Vec::new();
+        let mut index = 0;
+
+        while index < chars.len() {
+            let start = index;
+            let letter = chars[index];
+
+            let token = if *in_comment {
+                let (_, close) = self.block_comment.unwrap_or_default();
+                match rest(index).find(close) {
+                    Some(offset) => {
+                        index += rest(index)[..offset].chars().count() + close.chars().count();
+                        *in_comment = false;
+                    }
+                    None => index = chars.len(),
+                }
+                Token::Comment
+            } else if self
+                .line_comments
+                .iter()
+                .any(|marker| rest(index).starts_with(marker))
+            {
+                index = chars.len();
+                Token::Comment
+            } else if let Some((open, _)) = self
+                .block_comment
+                .filter(|(open, _)| rest(index).starts_with(open))
+            {
+                index += open.chars().count();
+                *in_comment = true;
+                Token::Comment
+            } else if self.quotes.contains(&letter) {
+                index += 1;
+                while index < chars.len() && chars[index] != letter {
+                    index += if chars[index] == '\\' { 2 } else { 1 };
+                }
+                index = (index + 1).min(chars.len());
+                Token::String
+            } else if letter.is_ascii_digit() {
+                while index < chars.len()
+                    && (chars[index].is_ascii_alphanumeric() || matches!(chars[index], '_' | '.'))
+                {
+                    index += 1;
+                }
+                Token::Number
+            } else if (self.atoms && matches!(letter, ':' | '@')
+                || self.variables && letter == '$'
+                || self.macros && letter == '#')
+                && chars.get(index + 1).is_some_and(|next| is_word(*next))
+            {
+                index += 1;
+                while index < chars.len() && is_word(chars[index]) {
+                    index += 1;
+                }
+                if letter == '#' {
+                    Token::Macro
+                } else {
+                    Token::Atom
+                }
+            } else if is_word(letter) {
+                while index < chars.len() && is_word(chars[index]) {
+                    index += 1;
+                }
+                let word: String = chars[start..index].iter().collect();
+
+                if self.keywords.contains(&word.as_str()) {
+                    Token::Keyword
+                } else if self.types.contains(&word.as_str()) {
+                    Token::Type
+                } else if self.literals.contains(&word.as_str()) {
+                    Token::Literal
+                } else if self.macros && chars.get(index) == Some(&'!') {
+                    index += 1;
+                    Token::Macro
+                } else if chars.get(index) == Some(&'(') {
+                    Token::Function
+                } else {
+                    Token::Plain
+                }
+            } else {
+                index += 1;
+                Token::Plain
+            };
+
+            push(&mut spans, chars[start..index].iter().collect(), token);
+        }
+
+        Line::from(spans)
+    }
+}
+
+fn is_word(letter: char) -> bool {
+    letter.is_alphanumeric() || letter == '_'
+}
+
+/// Appends a token, merging it into the previous span when they look alike.
+fn push(spans: &mut Vec<Span<'static>>, content: String, token: Token) {
+    let style = token.style();
+
+    match spans.last_mut() {
+        Some(last) if last.style == style => last.content.to_mut().push_str(&content),
+        _ => spans.push(Span::styled(content, style)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The tokens of a highlighted line, as content and style.
+    fn spans(line: &Line) -> Vec<(String, Style)> {
+        line.spans
+            .iter()
+            .map(|span| (span.content.to_string(), span.style))
+            .collect()
+    }
+
+    #[test]
+    fn grammars_are_found_by_name_or_alias() {
+        assert_eq!(grammar("Rust").map(|grammar| grammar.name), Some("rust"));
+        assert_eq!(grammar("exs").map(|grammar| grammar.name), Some("elixir"));
+        assert!(grammar("cobol").is_none());
+    }
+
+    #[test]
+    fn words_are_told_apart() {
+        let lines = highlight(grammar("rust"), &[r#"let x = parse("1") + 2; // x"#]);
+
+        assert_eq!(
+            spans(&lines[0]),
+            [
+                ("let".into(), Token::Keyword.style()),
+                (" x = ".into(), Token::Plain.style()),
+                ("parse".into(), Token::Function.style()),
+                ("(".into(), Token::Plain.style()),
+                (r#""1""#.into(), Token::String.style()),
+                (") + ".into(), Token::Plain.style()),
+                ("2".into(), Token::Number.style()),
+                ("; ".into(), Token::Plain.style()),
+                ("// x".into(), Token::Comment.style()),
+            ]
+        );
+    }
+
+    #[test]
+    fn block_comments_carry_over_to_the_next_line() {
+        let lines = highlight(grammar("cpp"), &["a /* b", "c */ d"]);
+
+        assert_eq!(
+            spans(&lines[1]),
+            [
+                ("c */".into(), Token::Comment.style()),
+                (" d".into(), Token::Plain.style()),
+            ]
+        );
+    }
+
+    #[test]
+    fn escaped_quotes_stay_in_the_string() {
+        let lines = highlight(grammar("lox"), &[r#"print "a\"b";"#]);
+
+        assert_eq!(
+            spans(&lines[0])[2],
+            (r#""a\"b""#.into(), Token::String.style())
+        );
+    }
+
+    #[test]
+    fn sigils_need_a_grammar_that_has_them() {
+        let elixir = highlight(grammar("elixir"), &[":ok"]);
+        let lox = highlight(grammar("lox"), &[":ok"]);
+
+        assert_eq!(spans(&elixir[0]), [(":ok".into(), Token::Atom.style())]);
+        assert_eq!(spans(&lox[0]), [(":ok".into(), Token::Plain.style())]);
+    }
+
+    #[test]
+    fn without_a_grammar_lines_stay_plain() {
+        let lines = highlight(None, &["fn main() {}"]);
+
+        assert_eq!(lines[0].style, Token::Plain.style());
+        assert_eq!(lines[0].spans[0].content, "fn main() {}");
+    }
+}
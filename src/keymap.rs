@@ -0,0 +1,348 @@
+//! Central keymap of the list view.
+//!
+//! Every binding the key handlers react to lives in the table of a
+//! [`Preset`], and the `?` help overlay is generated from the very same
+//! table, so the two can never tell different stories.
+
+use ratzilla::event::{KeyCode, KeyEvent};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Context {
+    Menu,
+    Content,
+    Prompt,
+    Game,
+    Overlay,
+}
+
+impl Context {
+    pub const ALL: [Context; 5] = [
+        Context::Menu,
+        Context::Content,
+        Context::Prompt,
+        Context::Game,
+        Context::Overlay,
+    ];
+
+    pub const fn title(self) -> &'static str {
+        match self {
+            Context::Menu => "Menu",
+            Context::Content => "Locked in",
+            Context::Prompt => "Typing",
+            Context::Game => "Games",
+            Context::Overlay => "Overlays",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Previous,
+    Next,
+    Select,
+    Expand,
+    Collapse,
+    Back,
+    Count,
+    ScrollUp,
+    ScrollDown,
+    ScrollLeft,
+    ScrollRight,
+    PageUp,
+    PageDown,
+    HalfPageUp,
+    HalfPageDown,
+    Top,
+    Bottom,
+    Help,
+    NextPreset,
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Drop,
+    Pause,
+    Quit,
+    Sort,
+    Filter,
+    Group,
+    Random,
+    Bookmark,
+    Contents,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chord {
+    Key(KeyCode),
+    Ctrl(char),
+    Alt(char),
+    /// Any of `0`-`9`, used for numeric prefixes.
+    Digit,
+}
+
+impl Chord {
+    pub fn matches(self, event: &KeyEvent) -> bool {
+        match self {
+            Chord::Key(code) => !event.ctrl && !event.alt && event.code == code,
+            Chord::Ctrl(key) => event.ctrl && event.code == KeyCode::Char(key),
+            Chord::Alt(key) => event.alt && event.code == KeyCode::Char(key),
+            Chord::Digit => {
+                !event.ctrl && matches!(event.code, KeyCode::Char(key) if key.is_ascii_digit())
+            }
+        }
+    }
+
+    pub fn label(self) -> String {
+        match self {
+            Chord::Key(KeyCode::Char(' ')) => "Space".into(),
+            Chord::Key(KeyCode::Char(key)) => key.into(),
+            Chord::Key(KeyCode::Up) => "↑".into(),
+            Chord::Key(KeyCode::Down) => "↓".into(),
+            Chord::Key(KeyCode::Left) => "←".into(),
+            Chord::Key(KeyCode::Right) => "→".into(),
+            Chord::Key(KeyCode::PageUp) => "PgUp".into(),
+            Chord::Key(KeyCode::PageDown) => "PgDn".into(),
+            Chord::Key(KeyCode::F(number)) => format!("F{number}"),
+            Chord::Key(code) => format!("{code:?}"),
+            Chord::Ctrl(key) => format!("C-{key}"),
+            Chord::Alt(key) => format!("M-{key}"),
+            Chord::Digit => "0-9".into(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Binding {
+    pub context: Context,
+    pub chords: &'static [Chord],
+    pub action: Action,
+    pub description: &'static str,
+}
+
+const fn bind(
+    context: Context,
+    chords: &'static [Chord],
+    action: Action,
+    description: &'static str,
+) -> Binding {
+    Binding {
+        context,
+        chords,
+        action,
+        description,
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    #[default]
+    Vim,
+    Emacs,
+    Arrows,
+}
+
+impl Preset {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Preset::Vim => "vim",
+            Preset::Emacs => "emacs",
+            Preset::Arrows => "arrows",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Preset::Vim, Preset::Emacs, Preset::Arrows]
+            .into_iter()
+            .find(|preset| preset.name() == name)
+    }
+
+    pub const fn next(self) -> Self {
+        match self {
+            Preset::Vim => Preset::Emacs,
+            Preset::Emacs => Preset::Arrows,
+            Preset::Arrows => Preset::Vim,
+        }
+    }
+
+    /// Caveats of the preset, shown at the top of the help.
+    pub const fn note(self) -> Option<&'static str> {
+        match self {
+            Preset::Emacs => Some(
+                "Browsers keep C-n and C-p for new windows and printing,\n\
+                 so lines move with M-n and M-p here. C-f, C-b and C-v\n\
+                 reach the page, but some browsers search, open the\n\
+                 bookmarks or paste first; the arrow keys always work.",
+            ),
+            Preset::Vim | Preset::Arrows => None,
+        }
+    }
+
+    const fn table(self) -> &'static [Binding] {
+        match self {
+            Preset::Vim => VIM,
+            Preset::Emacs => EMACS,
+            Preset::Arrows => ARROWS,
+        }
+    }
+
+    /// Looks up the action bound to `event` in the given context.
+    pub fn action(self, context: Context, event: &KeyEvent) -> Option<Action> {
+        self.bindings(context)
+            .find(|binding| binding.chords.iter().any(|chord| chord.matches(event)))
+            .map(|binding| binding.action)
+    }
+
+    /// Labels of every chord bound to `action`, joined for footer hints.
+    pub fn keys(self, context: Context, action: Action) -> String {
+        self.bindings(context)
+            .filter(|binding| binding.action == action)
+            .flat_map(|binding| binding.chords.iter().map(|chord| chord.label()))
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    pub fn bindings(self, context: Context) -> impl Iterator<Item = &'static Binding> {
+        self.table()
+            .iter()
+            .filter(move |binding| binding.context == context)
+    }
+}
+
+use Action::*;
+use Chord::*;
+use Context::*;
+
+/// Letters bound in the content pane stay clear of the title word, whose
+/// secret swallows the keys it matches before the keymap sees them.
+#[rustfmt::skip]
+const VIM: &[Binding] = &[
+    bind(Menu, &[Key(KeyCode::Up), Key(KeyCode::Char('k'))], Previous, "Select the previous page"),
+    bind(Menu, &[Key(KeyCode::Down), Key(KeyCode::Char('j'))], Next, "Select the next page"),
+    bind(Menu, &[Key(KeyCode::Enter)], Select, "Lock in to the selected page"),
+    bind(Menu, &[Key(KeyCode::Right), Key(KeyCode::Char('l'))], Expand, "Unfold the subpages, or lock in"),
+    bind(Menu, &[Key(KeyCode::Left), Key(KeyCode::Char('h'))], Collapse, "Fold the subpages"),
+    bind(Menu, &[Key(KeyCode::Char('?')), Key(KeyCode::F(1))], Help, "Show this help"),
+    bind(Menu, &[Key(KeyCode::F(2))], NextPreset, "Switch to the next keymap preset"),
+    bind(Content, &[Digit], Count, "Repeat the next motion, or pick a line for g/G"),
+    bind(Content, &[Key(KeyCode::Up), Key(KeyCode::Char('k'))], ScrollUp, "Scroll up a line"),
+    bind(Content, &[Key(KeyCode::Down), Key(KeyCode::Char('j'))], ScrollDown, "Scroll down a line"),
+    bind(Content, &[Key(KeyCode::Left), Key(KeyCode::Char('h'))], ScrollLeft, "Scroll code blocks left, or open the previous chapter"),
+    bind(Content, &[Key(KeyCode::Right), Key(KeyCode::Char('l'))], ScrollRight, "Scroll code blocks right, or open the next chapter"),
+    bind(Content, &[Key(KeyCode::PageUp), Key(KeyCode::Char('w'))], PageUp, "Scroll up a page"),
+    bind(Content, &[Key(KeyCode::PageDown), Key(KeyCode::Char(' '))], PageDown, "Scroll down a page"),
+    bind(Content, &[Ctrl('u')], HalfPageUp, "Scroll up half a page"),
+    bind(Content, &[Ctrl('d')], HalfPageDown, "Scroll down half a page"),
+    bind(Content, &[Key(KeyCode::Home), Key(KeyCode::Char('g'))], Top, "Go to the top"),
+    bind(Content, &[Key(KeyCode::End), Key(KeyCode::Char('G'))], Bottom, "Go to the end"),
+    bind(Content, &[Key(KeyCode::Esc), Key(KeyCode::Char('q'))], Back, "Return to the menu"),
+    bind(Content, &[Key(KeyCode::Char('?')), Key(KeyCode::F(1))], Help, "Show this help"),
+    bind(Content, &[Key(KeyCode::Char('s'))], Sort, "Sort the soundtrack by another column"),
+    bind(Content, &[Key(KeyCode::Char('f'))], Filter, "Show only the next genre of the soundtrack"),
+    bind(Content, &[Key(KeyCode::Char('c'))], Group, "Group the soundtrack by artist"),
+    bind(Content, &[Key(KeyCode::Char('p'))], Random, "Pick a random album from the soundtrack"),
+    bind(Content, &[Key(KeyCode::Char('m'))], Bookmark, "Bookmark the page being read, or delete the selected bookmark"),
+    bind(Content, &[Key(KeyCode::Char('o'))], Contents, "Go back to the contents of the book"),
+    bind(Content, &[Key(KeyCode::Enter), Key(KeyCode::Char('i'))], Select, "Start the selected game, read the book or open a chapter, or type at the prompt"),
+    bind(Prompt, &[Key(KeyCode::Esc)], Back, "Stop typing"),
+    bind(Prompt, &[Key(KeyCode::F(1))], Help, "Show this help"),
+    bind(Game, &[Key(KeyCode::Up), Key(KeyCode::Char('k')), Key(KeyCode::Char('w'))], MoveUp, "Move up, or rotate"),
+    bind(Game, &[Key(KeyCode::Down), Key(KeyCode::Char('j')), Key(KeyCode::Char('s'))], MoveDown, "Move down"),
+    bind(Game, &[Key(KeyCode::Left), Key(KeyCode::Char('h')), Key(KeyCode::Char('a'))], MoveLeft, "Move left"),
+    bind(Game, &[Key(KeyCode::Right), Key(KeyCode::Char('l')), Key(KeyCode::Char('d'))], MoveRight, "Move right"),
+    bind(Game, &[Key(KeyCode::Char(' '))], Drop, "Drop the piece"),
+    bind(Game, &[Key(KeyCode::Enter)], Select, "Play again after a game over"),
+    bind(Game, &[Key(KeyCode::Esc), Key(KeyCode::Char('p'))], Pause, "Pause the game"),
+    bind(Overlay, &[Key(KeyCode::Up), Key(KeyCode::Char('k'))], ScrollUp, "Scroll up a line"),
+    bind(Overlay, &[Key(KeyCode::Down), Key(KeyCode::Char('j'))], ScrollDown, "Scroll down a line"),
+    bind(Overlay, &[Key(KeyCode::Tab), Key(KeyCode::F(2))], NextPreset, "Switch to the next keymap preset"),
+    bind(Overlay, &[Key(KeyCode::Esc), Key(KeyCode::Enter)], Back, "Close the overlay, or resume the game"),
+    bind(Overlay, &[Key(KeyCode::Char('q'))], Quit, "Close the overlay, or quit the game"),
+];
+
+/// Emacs motions, minus the chords browsers never hand over to a page.
+#[rustfmt::skip]
+const EMACS: &[Binding] = &[
+    bind(Menu, &[Key(KeyCode::Up), Alt('p')], Previous, "Select the previous page"),
+    bind(Menu, &[Key(KeyCode::Down), Alt('n')], Next, "Select the next page"),
+    bind(Menu, &[Key(KeyCode::Enter), Ctrl('m')], Select, "Lock in to the selected page"),
+    bind(Menu, &[Key(KeyCode::Right), Ctrl('f')], Expand, "Unfold the subpages, or lock in"),
+    bind(Menu, &[Key(KeyCode::Left), Ctrl('b')], Collapse, "Fold the subpages"),
+    bind(Menu, &[Key(KeyCode::Char('?')), Key(KeyCode::F(1))], Help, "Show this help"),
+    bind(Menu, &[Key(KeyCode::F(2))], NextPreset, "Switch to the next keymap preset"),
+    bind(Content, &[Digit], Count, "Repeat the next motion, or pick a line for M-</M->"),
+    bind(Content, &[Key(KeyCode::Up), Alt('p')], ScrollUp, "Scroll up a line"),
+    bind(Content, &[Key(KeyCode::Down), Alt('n')], ScrollDown, "Scroll down a line"),
+    bind(Content, &[Key(KeyCode::Left), Ctrl('b')], ScrollLeft, "Scroll code blocks left, or open the previous chapter"),
+    bind(Content, &[Key(KeyCode::Right), Ctrl('f')], ScrollRight, "Scroll code blocks right, or open the next chapter"),
+    bind(Content, &[Key(KeyCode::PageUp), Alt('v')], PageUp, "Scroll up a page"),
+    bind(Content, &[Key(KeyCode::PageDown), Ctrl('v')], PageDown, "Scroll down a page"),
+    bind(Content, &[Key(KeyCode::Home), Alt('<')], Top, "Go to the top"),
+    bind(Content, &[Key(KeyCode::End), Alt('>')], Bottom, "Go to the end"),
+    bind(Content, &[Key(KeyCode::Esc), Ctrl('g')], Back, "Return to the menu"),
+    bind(Content, &[Key(KeyCode::Char('?')), Key(KeyCode::F(1))], Help, "Show this help"),
+    bind(Content, &[Alt('s')], Sort, "Sort the soundtrack by another column"),
+    bind(Content, &[Alt('f')], Filter, "Show only the next genre of the soundtrack"),
+    bind(Content, &[Alt('a')], Group, "Group the soundtrack by artist"),
+    bind(Content, &[Alt('r')], Random, "Pick a random album from the soundtrack"),
+    bind(Content, &[Alt('m')], Bookmark, "Bookmark the page being read, or delete the selected bookmark"),
+    bind(Content, &[Alt('o')], Contents, "Go back to the contents of the book"),
+    bind(Content, &[Key(KeyCode::Enter)], Select, "Start the selected game, read the book or open a chapter, or type at the prompt"),
+    bind(Prompt, &[Key(KeyCode::Esc), Ctrl('g')], Back, "Stop typing"),
+    bind(Prompt, &[Key(KeyCode::F(1))], Help, "Show this help"),
+    bind(Game, &[Key(KeyCode::Up), Alt('p')], MoveUp, "Move up, or rotate"),
+    bind(Game, &[Key(KeyCode::Down), Alt('n')], MoveDown, "Move down"),
+    bind(Game, &[Key(KeyCode::Left), Ctrl('b')], MoveLeft, "Move left"),
+    bind(Game, &[Key(KeyCode::Right), Ctrl('f')], MoveRight, "Move right"),
+    bind(Game, &[Key(KeyCode::Char(' '))], Drop, "Drop the piece"),
+    bind(Game, &[Key(KeyCode::Enter)], Select, "Play again after a game over"),
+    bind(Game, &[Key(KeyCode::Esc), Ctrl('g')], Pause, "Pause the game"),
+    bind(Overlay, &[Key(KeyCode::Up), Alt('p')], ScrollUp, "Scroll up a line"),
+    bind(Overlay, &[Key(KeyCode::Down), Alt('n')], ScrollDown, "Scroll down a line"),
+    bind(Overlay, &[Key(KeyCode::Tab), Key(KeyCode::F(2))], NextPreset, "Switch to the next keymap preset"),
+    bind(Overlay, &[Key(KeyCode::Esc), Key(KeyCode::Enter)], Back, "Close the overlay, or resume the game"),
+    bind(Overlay, &[Ctrl('g')], Quit, "Close the overlay, or quit the game"),
+];
+
+/// Layout independent bindings, for visitors who would rather not hunt
+/// for letters on a Turkish-Q or Dvorak keyboard.
+#[rustfmt::skip]
+const ARROWS: &[Binding] = &[
+    bind(Menu, &[Key(KeyCode::Up)], Previous, "Select the previous page"),
+    bind(Menu, &[Key(KeyCode::Down)], Next, "Select the next page"),
+    bind(Menu, &[Key(KeyCode::Enter)], Select, "Lock in to the selected page"),
+    bind(Menu, &[Key(KeyCode::Right)], Expand, "Unfold the subpages, or lock in"),
+    bind(Menu, &[Key(KeyCode::Left)], Collapse, "Fold the subpages"),
+    bind(Menu, &[Key(KeyCode::F(1))], Help, "Show this help"),
+    bind(Menu, &[Key(KeyCode::F(2))], NextPreset, "Switch to the next keymap preset"),
+    bind(Content, &[Key(KeyCode::Up)], ScrollUp, "Scroll up a line"),
+    bind(Content, &[Key(KeyCode::Down)], ScrollDown, "Scroll down a line"),
+    bind(Content, &[Key(KeyCode::F(8))], ScrollLeft, "Scroll code blocks left, or open the previous chapter"),
+    bind(Content, &[Key(KeyCode::Right), Key(KeyCode::F(9))], ScrollRight, "Scroll code blocks right, or open the next chapter"),
+    bind(Content, &[Key(KeyCode::PageUp)], PageUp, "Scroll up a page"),
+    bind(Content, &[Key(KeyCode::PageDown)], PageDown, "Scroll down a page"),
+    bind(Content, &[Key(KeyCode::Home)], Top, "Go to the top"),
+    bind(Content, &[Key(KeyCode::End)], Bottom, "Go to the end"),
+    bind(Content, &[Key(KeyCode::Esc), Key(KeyCode::Left), Key(KeyCode::Backspace)], Back, "Return to the menu"),
+    bind(Content, &[Key(KeyCode::F(1))], Help, "Show this help"),
+    bind(Content, &[Key(KeyCode::F(3))], Sort, "Sort the soundtrack by another column"),
+    bind(Content, &[Key(KeyCode::F(4))], Filter, "Show only the next genre of the soundtrack"),
+    bind(Content, &[Key(KeyCode::F(6))], Group, "Group the soundtrack by artist"),
+    bind(Content, &[Key(KeyCode::F(7))], Random, "Pick a random album from the soundtrack"),
+    bind(Content, &[Key(KeyCode::F(10))], Bookmark, "Bookmark the page being read, or delete the selected bookmark"),
+    bind(Content, &[Key(KeyCode::Tab)], Contents, "Go back to the contents of the book"),
+    bind(Content, &[Key(KeyCode::Enter)], Select, "Start the selected game, read the book or open a chapter, or type at the prompt"),
+    bind(Prompt, &[Key(KeyCode::Esc)], Back, "Stop typing"),
+    bind(Prompt, &[Key(KeyCode::F(1))], Help, "Show this help"),
+    bind(Game, &[Key(KeyCode::Up)], MoveUp, "Move up, or rotate"),
+    bind(Game, &[Key(KeyCode::Down)], MoveDown, "Move down"),
+    bind(Game, &[Key(KeyCode::Left)], MoveLeft, "Move left"),
+    bind(Game, &[Key(KeyCode::Right)], MoveRight, "Move right"),
+    bind(Game, &[Key(KeyCode::Char(' '))], Drop, "Drop the piece"),
+    bind(Game, &[Key(KeyCode::Enter)], Select, "Play again after a game over"),
+    bind(Game, &[Key(KeyCode::Esc)], Pause, "Pause the game"),
+    bind(Overlay, &[Key(KeyCode::Up)], ScrollUp, "Scroll up a line"),
+    bind(Overlay, &[Key(KeyCode::Down)], ScrollDown, "Scroll down a line"),
+    bind(Overlay, &[Key(KeyCode::Tab), Key(KeyCode::F(2))], NextPreset, "Switch to the next keymap preset"),
+    bind(Overlay, &[Key(KeyCode::Esc), Key(KeyCode::Enter)], Back, "Close the overlay, or resume the game"),
+    bind(Overlay, &[Key(KeyCode::Backspace)], Quit, "Close the overlay, or quit the game"),
+];
@@ -1,19 +1,59 @@
+mod achievements;
+mod ansi;
+// Runs in build.rs, and is only compiled here to be tested.
+#[cfg(test)]
+mod chapter;
+mod chart;
+mod dictionary;
+mod document;
+mod figlet;
+mod focus;
+mod games;
+mod gdb;
+mod gradient;
+mod highlight;
+mod keymap;
+mod menu;
+mod random;
+mod raster;
+mod reader;
+mod repl;
+mod secrets;
+mod shell;
+mod soundtrack;
+mod storage;
 mod text;
+mod title;
+mod vfs;
 use std::{cell::RefCell, io, rc::Rc};
 
 use ratatui::{
-    layout::{Alignment, Flex},
+    layout::Flex,
     prelude::*,
     style::{Color, Stylize},
-    symbols::scrollbar,
     widgets::*,
     Frame, Terminal,
 };
 
-use ratzilla::{event::KeyCode, DomBackend, WebRenderer};
+use ratzilla::{event::KeyEvent, DomBackend, WebRenderer};
 
 use web_time::{Duration, Instant};
 
+use achievements::Achievements;
+use dictionary::Dictionary;
+use figlet::Font;
+use focus::{FocusStack, Layer, Overlay};
+use games::Arcade;
+use gdb::Gdb;
+use gradient::{Direction, Gradient};
+use keymap::{Action, Context, Preset};
+use menu::{Entry, Tree};
+use reader::Reader;
+use repl::{lisp::Lisp, lox::Lox, Repl};
+use secrets::{Effect, Secrets};
+use soundtrack::Soundtrack;
+use title::{Encoding, Title};
+
 fn main() -> io::Result<()> {
     std::panic::set_hook(Box::new(console_error_panic_hook::hook));
     let _ = console_log::init_with_level(log::Level::Debug);
@@ -135,51 +175,136 @@ impl Background {
 
 #[derive(Debug)]
 struct App {
-    title: &'static str,
+    title: Title,
     status: AppStatus,
+    started_at: Instant,
     last_instant: Instant,
     intro_finalized: bool,
     intro_finalized_at: Instant,
-    list_state: ListState,
-    locked_in: bool,
+    tree: Tree,
+    focus: FocusStack,
+    keymap: Preset,
+    secrets: Secrets,
+    achievements: Achievements,
+    arcade: Arcade,
+    soundtrack: Soundtrack,
+    gdb: Gdb,
+    lisp: Repl,
+    lox: Repl,
+    dictionary: Dictionary,
+    readers: Vec<Reader>,
+    party: bool,
+    monochrome: bool,
+    banner: Vec<String>,
+    press_any_key: Text<'static>,
+    compact_name: Vec<String>,
+    compact_press_any_key: Vec<String>,
     scrollbar_state: ScrollbarState,
     scroll: u16,
+    horizontal_scroll: u16,
+    code_overflow: u16,
+    scrolls: [u16; menu::SLOTS],
+    /// Whether the next frame scrolls down to the gdb prompt, whose page
+    /// grows as it is typed into.
+    follow_prompt: bool,
+    count: Option<u16>,
+    page_height: u16,
+    content_length: u16,
+    /// The page `content_length` was measured for.
+    content_slot: usize,
     background: Background,
 }
 
 impl Default for App {
     fn default() -> Self {
+        let scrolls = App::load_scrolls();
+        let secrets = Secrets::default();
+        let mut title = Title::new(
+            text::TITLE_WORD,
+            text::TITLE_MESSAGE,
+            Encoding::from_name(text::TITLE_ENCODING).unwrap_or_default(),
+        );
+        title.decode(secrets.progress(secrets::TARBETU), Instant::now());
+
         Self {
-            title: text::TARBETU,
+            title,
             status: AppStatus::default(),
+            started_at: Instant::now(),
             last_instant: Instant::now(),
             intro_finalized: false,
             intro_finalized_at: Instant::now(),
-            list_state: ListState::default().with_selected(Some(0)),
+            tree: Tree::default(),
             scrollbar_state: ScrollbarState::default(),
-            scroll: 0,
-            locked_in: false,
+            scroll: scrolls[0],
+            horizontal_scroll: 0,
+            code_overflow: 0,
+            scrolls,
+            follow_prompt: false,
+            count: None,
+            page_height: 0,
+            content_length: 0,
+            content_slot: usize::MAX,
+            focus: FocusStack::default(),
+            keymap: storage::get("keymap")
+                .and_then(|name| Preset::from_name(&name))
+                .unwrap_or_default(),
+            secrets,
+            achievements: Achievements::default(),
+            arcade: Arcade::load(),
+            soundtrack: Soundtrack::default(),
+            gdb: Gdb::default(),
+            lisp: Repl::new(Box::new(Lisp::default())),
+            lox: Repl::new(Box::new(Lox::default())),
+            dictionary: Dictionary::default(),
+            readers: reader::BOOKS.iter().map(Reader::new).collect(),
+            party: false,
+            monochrome: raster::prefers_monochrome(),
+            banner: App::banner(text::BANNER_FONT, text::TITLE_WORD),
+            press_any_key: ansi::parse(
+                include_bytes!("../assets/ansi/press_any_key.ans"),
+                ansi::DEFAULT_WIDTH,
+            ),
+            compact_name: App::banner(text::BANNER_FONT, text::INTRO_NAME_BANNER),
+            compact_press_any_key: App::banner(text::BANNER_FONT, text::PRESS_ANY_KEY_BANNER),
             background: Background::default(),
         }
     }
 }
 
 impl App {
-    const fn menu_length() -> usize {
-        7
+    /// Pages made of prose, all of which, subpages included, have to be
+    /// read for the bookworm achievement. They are the ones the menu lists
+    /// before the games.
+    const READABLE_PAGES: usize = vfs::GAMES_PAGE;
+    const READABLE_SLOTS: usize = App::READABLE_PAGES + vfs::SUBPAGES;
+    /// `./portfolio/tarlox`, second of the portfolio's subpages.
+    const TARLOX_SUBPAGE: usize = 1;
+    const DUCKTONGUE_SUBPAGE: usize = 2;
+    /// Height of the content pane below which a REPL or the dictionary
+    /// pushes the prose of its page out.
+    const WIDGET_MIN_HEIGHT: u16 = 16;
+    /// Columns code blocks move per sideways scroll.
+    const HORIZONTAL_STEP: u16 = 4;
+
+    /// Renders `word` with a bundled FIGlet font, without the blank rows
+    /// fonts keep below the baseline.
+    fn banner(font: &str, word: &str) -> Vec<String> {
+        let mut rows = Font::bundled(font)
+            .map(|font| font.render(word))
+            .unwrap_or_default();
+
+        while rows.last().is_some_and(|row| row.is_empty()) {
+            rows.pop();
+        }
+        rows
     }
 
-    // fn menu() -> Vec<ListItem<'static>> {
-    fn menu() -> [ListItem<'static>; App::menu_length()] {
-        [
-            ListItem::new("./tarbetu"),
-            ListItem::new("./portfolio"),
-            ListItem::new("./translations"),
-            ListItem::new("./lycian"),
-            ListItem::new("./personal_soundtrack"),
-            ListItem::new("./echoes_from_my_mania"),
-            ListItem::new("./kara_tilki_hiyerarsisi"),
-        ]
+    fn page_count(&self) -> usize {
+        if self.secrets.is_found("sudo") {
+            vfs::PAGES
+        } else {
+            vfs::PAGES - 1
+        }
     }
 
     fn run(app: Rc<RefCell<Self>>) -> io::Result<()> {
@@ -188,6 +313,10 @@ impl App {
 
         app.borrow_mut().last_instant = Instant::now();
 
+        if let Some(route) = vfs::location_route() {
+            app.borrow_mut().open_route(&route);
+        }
+
         let event_app = app.clone();
         terminal.on_key_event(move |event| {
             use AppStatus::*;
@@ -195,13 +324,13 @@ impl App {
             let mut app = event_app.borrow_mut();
             match app.status {
                 List => {
-                    app.handle_event(event.code)
-                    // app.list_state.select_next();
+                    app.handle_event(event)
+                    // app.tree.select_next();
                 }
                 _ => {
                     app.intro_finalized = true;
                     app.intro_finalized_at = Instant::now();
-                    app.status = AppStatus::List;
+                    app.enter_list();
                 }
             }
         });
@@ -215,6 +344,7 @@ impl App {
                 if next_status == app.status {
                     app.intro_finalized = true;
                     app.intro_finalized_at = Instant::now();
+                    app.achievements.unlock("intro", Instant::now());
                 }
 
                 app.status = app.next_status();
@@ -230,7 +360,8 @@ impl App {
                 app.last_instant = Instant::now()
             }
 
-            if app.intro_finalized && app.last_instant.elapsed() >= Duration::from_millis(500) {
+            let background_interval = Duration::from_millis(if app.party { 100 } else { 500 });
+            if app.intro_finalized && app.last_instant.elapsed() >= background_interval {
                 app.background = app.background.next();
                 app.last_instant = Instant::now()
             }
@@ -238,7 +369,11 @@ impl App {
             if app.status == AppStatus::IntroductionIdle
                 && app.intro_finalized_at.elapsed() >= Duration::from_secs(5)
             {
-                app.status = AppStatus::List;
+                app.enter_list();
+            }
+
+            if app.focus.active() == &Layer::Game {
+                app.arcade.tick(Instant::now());
             }
 
             app.render(frame);
@@ -246,102 +381,230 @@ impl App {
         Ok(())
     }
 
-    fn handle_event(&mut self, key: KeyCode) {
-        match key {
-            KeyCode::Enter if !self.locked_in => self.locked_in = true,
-            KeyCode::Esc if self.locked_in => self.locked_in = false,
-            KeyCode::Char('t') if self.title == text::TARBETU => {
-                self.title = text::TARBETU1;
-            }
-            KeyCode::Char('a') if self.title == text::TARBETU1 => {
-                self.title = text::TARBETU2;
-            }
-            KeyCode::Char('r') if self.title == text::TARBETU2 => {
-                self.title = text::TARBETU3;
-            }
-            KeyCode::Char('b') if self.title == text::TARBETU3 => {
-                self.title = text::TARBETU4;
-            }
-            KeyCode::Char('e') if self.title == text::TARBETU4 => {
-                self.title = text::TARBETU5;
-            }
-            KeyCode::Char('t') if self.title == text::TARBETU5 => {
-                self.title = text::TARBETU6;
-            }
-            KeyCode::Char('u') if self.title == text::TARBETU6 => {
-                self.title = text::TARBETU7;
-            }
-            KeyCode::Up | KeyCode::Char('k') => {
-                if self.locked_in {
-                    self.scroll = self.scroll.saturating_sub(1);
-                } else {
-                    self.scroll = 0;
-                    self.list_state.select_previous();
+    fn enter_list(&mut self) {
+        self.status = AppStatus::List;
+        self.title.scramble(Instant::now());
+    }
+
+    fn handle_event(&mut self, event: KeyEvent) {
+        match self.focus.active() {
+            Layer::Menu => self.handle_menu_event(event),
+            Layer::Content | Layer::Reader => self.handle_content_event(event),
+            Layer::Prompt => self.handle_prompt_event(event),
+            Layer::Game => self.handle_game_event(event),
+            Layer::Overlay(_) => self.handle_overlay_event(event),
+        }
+
+        self.remember_scroll();
+        self.scrollbar_state = self.scrollbar_state.position(self.scroll as usize);
+    }
+
+    fn next_keymap(&mut self) {
+        self.keymap = self.keymap.next();
+        storage::set("keymap", self.keymap.name());
+    }
+
+    /// Feeds a key to the easter egg registry, returning whether the key
+    /// was swallowed by one of them.
+    fn handle_secret(&mut self, event: &KeyEvent) -> bool {
+        if event.ctrl || event.alt {
+            return false;
+        }
+
+        let now = Instant::now();
+        let outcome = self.secrets.feed(event.code, now);
+        self.title
+            .decode(self.secrets.progress(secrets::TARBETU), now);
+
+        for name in outcome.found {
+            self.achievements.unlock(name, now);
+        }
+
+        for effect in outcome.effects {
+            match effect {
+                Effect::Popup { title, message } => {
+                    self.focus
+                        .push(Layer::Overlay(Overlay::Popup { title, message }));
                 }
-            }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if self.locked_in {
-                    self.scroll = self.scroll.saturating_add(1);
-                } else {
-                    self.scroll = 0;
-                    self.list_state.select_next();
+                Effect::UnlockPage => {
+                    let pages = self.page_count();
+                    self.tree.select(Entry::page(vfs::HIDDEN_PAGE), pages);
+                    self.page_changed();
                 }
+                Effect::Party => self.party = !self.party,
             }
-            _ => {}
         }
 
-        self.scrollbar_state = self.scrollbar_state.position(self.scroll as usize);
+        outcome.consumed
+    }
+
+    fn selected_entry(&self) -> Entry {
+        self.tree.selected(self.page_count())
+    }
+
+    fn selected_page(&self) -> usize {
+        self.selected_entry().page
+    }
+
+    fn load_scrolls() -> [u16; menu::SLOTS] {
+        let mut scrolls = [0; menu::SLOTS];
+
+        if let Some(saved) = storage::get("scrolls") {
+            for (scroll, value) in scrolls.iter_mut().zip(saved.split(',')) {
+                *scroll = value.parse().unwrap_or_default();
+            }
+        }
+
+        scrolls
+    }
+
+    /// Stores the scroll offset of the selected page, persisting it
+    /// across reloads whenever it changes.
+    fn remember_scroll(&mut self) {
+        let slot = self.selected_entry().slot();
+
+        // The pane clamps the scroll when it draws, which may not have
+        // happened yet. Only the page drawn last has a known length.
+        if slot == self.content_slot {
+            self.scroll = self.scroll.min(self.max_scroll());
+        }
+
+        if self.scrolls[slot] != self.scroll {
+            self.scrolls[slot] = self.scroll;
+
+            let saved = self
+                .scrolls
+                .iter()
+                .map(u16::to_string)
+                .collect::<Vec<_>>()
+                .join(",");
+            storage::set("scrolls", &saved);
+        }
+    }
+
+    /// Brings back the scroll of the newly selected page and puts its
+    /// route in the address bar.
+    fn page_changed(&mut self) {
+        self.restore_scroll();
+        vfs::set_location_route(&self.selected_entry().route());
+    }
+
+    /// Opens the page a route such as `./portfolio/tarlox` points at,
+    /// skipping the intro. Hidden pages stay hidden until found.
+    fn open_route(&mut self, route: &str) {
+        let pages = self.page_count();
+        let Some(entry) = vfs::route(route).filter(|entry| entry.page < pages) else {
+            return;
+        };
+
+        self.tree.select(entry, pages);
+        self.restore_scroll();
+        self.intro_finalized = true;
+        self.intro_finalized_at = Instant::now();
+        self.enter_list();
+        self.focus.push(Layer::Content);
+    }
+
+    fn restore_scroll(&mut self) {
+        self.scroll = self.scrolls[self.selected_entry().slot()];
+        self.horizontal_scroll = 0;
     }
 
     fn render<'a>(&mut self, frame: &mut Frame<'a>) {
         use AppStatus::*;
 
         match self.status {
-            IntroductionStart => {
-                self.render_introduction(frame, text::NAME1, Color::Cyan);
-            }
-            Introduction(0) => {
-                self.render_introduction(frame, text::NAME1, Color::LightCyan);
-            }
-            Introduction(1) => {
-                self.render_introduction(frame, text::NAME2, Color::Yellow);
+            IntroductionStart | Introduction(0) => {
+                self.render_name(frame, text::NAME1, gradient::OCEAN, Direction::Horizontal);
             }
-            Introduction(2) => {
-                self.render_introduction(frame, text::NAME2, Color::LightYellow);
+            Introduction(1) | Introduction(2) => {
+                self.render_name(frame, text::NAME2, gradient::SUNSET, Direction::Vertical);
             }
-            Introduction(3) => {
-                self.render_introduction(frame, text::NAME3, Color::Red);
-            }
-            Introduction(4) => {
-                self.render_introduction(frame, text::NAME3, Color::LightRed);
+            Introduction(3) | Introduction(4) => {
+                self.render_name(frame, text::NAME3, gradient::EMBER, Direction::Diagonal);
             }
             Introduction(5) | IntroductionIdle => {
-                self.render_introduction(frame, text::PRESS_ANY_KEY, Color::LightGreen);
+                self.render_press_any_key(frame, self.press_any_key.clone());
             }
             Introduction(6) => {
-                self.render_introduction(frame, text::PRESS_ANY_KEY, Color::Green);
+                self.render_press_any_key(frame, App::plain_art(text::PRESS_ANY_KEY, Color::Green));
             }
             List => {
                 self.background.render(frame);
                 self.render_list_view(frame);
             }
             _ => {
-                self.render_introduction(frame, text::PRESS_ANY_KEY, Color::Green);
+                self.render_press_any_key(frame, App::plain_art(text::PRESS_ANY_KEY, Color::Green));
             }
         }
     }
 
-    fn render_introduction<'a>(&self, frame: &mut Frame<'a>, text: &'a str, color: Color) {
-        let ascii_art = Text::from(
+    fn plain_art(text: &str, color: Color) -> Text<'_> {
+        Text::from(
             text.split("\n")
                 .map(|line| Line::from(line.fg(color)))
                 .collect::<Vec<Line>>(),
-        );
+        )
+    }
+
+    /// The largest of the variants, ordered from largest to smallest, that
+    /// fits in `area`, or the smallest when none does. Checked on every
+    /// draw, so resizing the window swaps variants right away.
+    fn fitting<'a, const N: usize>(area: Rect, variants: [Text<'a>; N]) -> Option<Text<'a>> {
+        let fits = |art: &Text| {
+            art.width() <= usize::from(area.width) && art.height() <= usize::from(area.height)
+        };
+        let mut variants = variants.into_iter().peekable();
+
+        while let Some(art) = variants.next() {
+            if fits(&art) || variants.peek().is_none() {
+                return Some(art);
+            }
+        }
+        None
+    }
+
+    /// A name frame whose colors flow through `stops` while the intro
+    /// plays, falling back to the FIGlet name and then the bare name on
+    /// small screens.
+    fn render_name(
+        &self,
+        frame: &mut Frame,
+        art: &str,
+        stops: &'static [[u8; 3]],
+        direction: Direction,
+    ) {
+        let variants = [
+            Text::from_iter(art.split('\n')),
+            Text::from_iter(self.compact_name.iter().map(String::as_str)),
+            Text::from(text::INTRO_NAME),
+        ];
+        let Some(art) = App::fitting(frame.area(), variants) else {
+            return;
+        };
+
+        let gradient = Gradient::new(stops, direction)
+            .cycle(self.started_at.elapsed(), Duration::from_secs(3));
+        self.render_introduction(frame, gradient.paint_text(art));
+    }
+
+    fn render_press_any_key(&self, frame: &mut Frame, art: Text) {
+        let variants = [
+            art,
+            Text::from_iter(self.compact_press_any_key.iter().map(String::as_str)).green(),
+            Text::from(text::PRESS_ANY_KEY_TEXT).green(),
+        ];
 
+        if let Some(art) = App::fitting(frame.area(), variants) {
+            self.render_introduction(frame, art);
+        }
+    }
+
+    fn render_introduction(&self, frame: &mut Frame, ascii_art: Text) {
         let area = App::center(
             frame.area(),
             Constraint::Length(ascii_art.width() as u16),
-            Constraint::Percentage(60),
+            Constraint::Length(ascii_art.height() as u16),
         );
 
         frame.render_widget(ascii_art, area);
@@ -385,6 +648,36 @@ impl App {
         self.render_footer(frame, footer_area);
         self.render_list(frame, list_area);
         self.render_content(frame, content_area);
+        self.render_overlays(frame, main_area);
+        self.render_toast(frame, main_area);
+    }
+
+    fn render_toast(&self, frame: &mut Frame, area: Rect) {
+        let Some(achievement) = self.achievements.toast(Instant::now()) else {
+            return;
+        };
+
+        let [_, toast_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(4)]).areas(area);
+        let [_, toast_area] =
+            Layout::horizontal([Constraint::Fill(1), Constraint::Length(48)]).areas(toast_area);
+
+        let toast = Paragraph::new(vec![
+            Line::from(achievement.name).bold(),
+            Line::from(achievement.description),
+        ])
+        .block(
+            Block::bordered()
+                .title(" Achievement unlocked ")
+                .border_set(symbols::border::QUADRANT_OUTSIDE)
+                .border_style(Style::default().fg(Color::LightMagenta)),
+        )
+        .bg(Color::Rgb(15, 15, 20))
+        .fg(Color::LightCyan)
+        .wrap(Wrap { trim: true });
+
+        frame.render_widget(Clear, toast_area);
+        frame.render_widget(toast, toast_area);
     }
 
     fn clear_areas(&self, frame: &mut Frame, areas: &[Rect]) {
@@ -394,103 +687,29 @@ impl App {
     }
 
     fn render_header(&self, frame: &mut Frame, area: Rect) {
-        frame.render_widget(Paragraph::new(self.title).bold().centered(), area);
-    }
-
-    fn render_footer(&self, frame: &mut Frame, area: Rect) {
         frame.render_widget(
-            Paragraph::new(if !self.locked_in {
-                "Use ↓↑ or j/k to navigate, Enter to locked in"
-            } else {
-                "Use ↓↑ or j/k to scroll, Esc to return menu"
-            })
-            .centered(),
-            area,
-        )
-    }
-
-    fn render_list(&mut self, frame: &mut Frame, area: Rect) {
-        let list_block = Block::bordered()
-            .border_set(if !self.locked_in {
-                symbols::border::QUADRANT_OUTSIDE
-            } else {
-                symbols::border::EMPTY
-            })
-            .border_style(Style::default().fg(Color::LightMagenta))
-            .bg(Color::Rgb(15, 15, 20))
-            .fg(if !self.locked_in {
-                Color::LightCyan
-            } else {
-                Color::Cyan
-            });
-
-        let list = List::new(App::menu())
-            .block(list_block)
-            .highlight_style(Style::default().fg(Color::LightMagenta))
-            .highlight_symbol("▶ ")
-            .highlight_spacing(HighlightSpacing::Always);
-
-        frame.render_stateful_widget(list, area, &mut self.list_state);
-    }
-
-    fn render_content(&mut self, frame: &mut Frame, area: Rect) {
-        let content_block = Block::bordered()
-            .border_set(if self.locked_in {
-                symbols::border::QUADRANT_OUTSIDE
-            } else {
-                symbols::border::EMPTY
-            })
-            .border_style(Style::default().fg(Color::LightMagenta))
-            .bg(Color::Rgb(15, 15, 20))
-            .padding(Padding::new(1, 2, 0, 0))
-            .fg(Color::LightCyan);
-
-        self.render_text(
-            frame,
-            content_block,
+            Paragraph::new(self.title.line(Instant::now()))
+                .bold()
+                .centered(),
             area,
-            match self.list_state.selected() {
-                Some(0) => text::ABOUT,
-                Some(1) => text::PORTFOLIO,
-                Some(2) => text::TRANSLATIONS,
-                Some(3) => text::LYCIAN_PROJECT,
-                Some(4) => text::MUSIC,
-                Some(5) => text::ECHOES,
-                Some(6) => text::KTH,
-                _ => "",
-            },
         );
     }
 
-    fn render_text(&mut self, frame: &mut Frame, block: Block, area: Rect, text: &'static str) {
-        let lines: Vec<Line> = text
-            .split('\n')
-            .map(|line| {
-                Line::from(if line.starts_with(">>>") {
-                    line.add_modifier(Modifier::BOLD)
-                } else if line.starts_with("http") {
-                    line.light_blue().style(Modifier::SLOW_BLINK)
-                } else {
-                    Span::from(line)
-                })
-            })
-            .collect();
-
-        self.scrollbar_state = self.scrollbar_state.content_length(lines.len());
-
-        let text = Paragraph::new(lines)
-            .block(block)
-            .alignment(Alignment::Left)
-            .wrap(Wrap { trim: true })
-            .scroll((self.scroll, 0));
-
-        frame.render_widget(text, area);
-
-        frame.render_stateful_widget(
-            Scrollbar::new(ScrollbarOrientation::VerticalRight).symbols(scrollbar::VERTICAL),
-            area,
-            &mut self.scrollbar_state,
-        );
+    fn render_footer(&self, frame: &mut Frame, area: Rect) {
+        let mut hints = self.focus.active().hints(self.keymap);
+        if self.focus.active() == &Layer::Content && self.has_prompt() {
+            hints = format!(
+                "Press {} to type. {hints}",
+                self.keymap.keys(Context::Content, Action::Select)
+            );
+        } else if self.focus.active() == &Layer::Content && self.has_reader() {
+            hints = format!(
+                "Press {} to read the book. {hints}",
+                self.keymap.keys(Context::Content, Action::Select)
+            );
+        }
+
+        frame.render_widget(Paragraph::new(hints).centered(), area)
     }
 
     /// Centers a [`Rect`] within another [`Rect`] using the provided [`Constraint`]s.
@@ -0,0 +1,215 @@
+//! The tree of pages in the list pane.
+//!
+//! Pages whose directory holds subdirectories unfold into one row per
+//! subdirectory, each with a page of its own, while the page itself keeps
+//! showing the summary. The list selects rows, which the tree maps back to
+//! the page, and subpage, they stand for.
+
+use ratatui::widgets::{ListItem, ListState};
+
+use crate::{vfs, vfs::Node};
+
+/// Every page and subpage, each with a scroll offset of its own.
+pub const SLOTS: usize = vfs::PAGES + vfs::SUBPAGES;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    pub page: usize,
+    /// Which subdirectory of the page, if the entry is not the page itself.
+    pub subpage: Option<usize>,
+}
+
+impl Entry {
+    pub const fn page(page: usize) -> Self {
+        Self {
+            page,
+            subpage: None,
+        }
+    }
+
+    pub fn node(self) -> Option<&'static Node> {
+        let page = *vfs::pages().get(self.page)?;
+
+        match self.subpage {
+            Some(subpage) => page.subpages().nth(subpage),
+            None => Some(page),
+        }
+    }
+
+    pub fn route(self) -> String {
+        let pages = vfs::pages();
+        let Some(page) = pages.get(self.page) else {
+            return ".".into();
+        };
+
+        let route = match self
+            .subpage
+            .and_then(|subpage| page.subpages().nth(subpage))
+        {
+            Some(subpage) => format!("./{}/{}", page.name, subpage.name),
+            None => format!("./{}", page.name),
+        };
+
+        match vfs::ALIASES.iter().find(|(_, target)| *target == route) {
+            Some((alias, _)) => alias.to_string(),
+            None => route,
+        }
+    }
+
+    /// Where the entry keeps its scroll offset: pages first, then the
+    /// subpages in menu order.
+    pub fn slot(self) -> usize {
+        let Some(subpage) = self.subpage else {
+            return self.page;
+        };
+
+        let before: usize = vfs::pages()
+            .iter()
+            .take(self.page)
+            .map(|page| page.subpages().count())
+            .sum();
+
+        vfs::PAGES + before + subpage
+    }
+}
+
+#[derive(Debug)]
+pub struct Tree {
+    state: ListState,
+    expanded: [bool; vfs::PAGES],
+}
+
+impl Default for Tree {
+    fn default() -> Self {
+        Self {
+            state: ListState::default().with_selected(Some(0)),
+            expanded: [false; vfs::PAGES],
+        }
+    }
+}
+
+impl Tree {
+    /// The visible rows, given how many pages are listed.
+    fn rows(&self, pages: usize) -> Vec<Entry> {
+        let mut rows = Vec::new();
+
+        for (page, node) in vfs::pages().into_iter().take(pages).enumerate() {
+            rows.push(Entry::page(page));
+
+            if self.expanded[page] {
+                rows.extend((0..node.subpages().count()).map(|subpage| Entry {
+                    page,
+                    subpage: Some(subpage),
+                }));
+            }
+        }
+        rows
+    }
+
+    fn position(&self, rows: &[Entry]) -> usize {
+        self.state
+            .selected()
+            .unwrap_or_default()
+            .min(rows.len().saturating_sub(1))
+    }
+
+    pub fn selected(&self, pages: usize) -> Entry {
+        let rows = self.rows(pages);
+
+        rows.get(self.position(&rows))
+            .copied()
+            .unwrap_or(Entry::page(0))
+    }
+
+    /// Selects an entry, unfolding its page if it is a subpage.
+    pub fn select(&mut self, entry: Entry, pages: usize) {
+        if entry.subpage.is_some() {
+            self.expanded[entry.page] = true;
+        }
+
+        let rows = self.rows(pages);
+        if let Some(position) = rows.iter().position(|row| *row == entry) {
+            self.state.select(Some(position));
+        }
+    }
+
+    pub fn select_previous(&mut self, pages: usize) {
+        let rows = self.rows(pages);
+        self.state
+            .select(Some(self.position(&rows).saturating_sub(1)));
+    }
+
+    pub fn select_next(&mut self, pages: usize) {
+        let rows = self.rows(pages);
+        let last = rows.len().saturating_sub(1);
+        self.state
+            .select(Some((self.position(&rows) + 1).min(last)));
+    }
+
+    /// Unfolds the selected page, or steps into its first subpage when it
+    /// is unfolded already. Returns whether there was anything to unfold.
+    pub fn expand(&mut self, pages: usize) -> bool {
+        let entry = self.selected(pages);
+        let has_subpages = entry.subpage.is_none()
+            && entry
+                .node()
+                .is_some_and(|node| node.subpages().next().is_some());
+
+        if !has_subpages {
+            return false;
+        }
+
+        if self.expanded[entry.page] {
+            self.select_next(pages);
+        } else {
+            self.expanded[entry.page] = true;
+        }
+        true
+    }
+
+    /// Steps out of a subpage to its page, or folds an unfolded page.
+    /// Returns whether the selection moved.
+    pub fn collapse(&mut self, pages: usize) -> bool {
+        let entry = self.selected(pages);
+
+        if entry.subpage.is_some() {
+            self.select(Entry::page(entry.page), pages);
+            return true;
+        }
+
+        self.expanded[entry.page] = false;
+        false
+    }
+
+    pub fn items(&self, pages: usize) -> Vec<ListItem<'static>> {
+        let nodes = vfs::pages();
+
+        self.rows(pages)
+            .into_iter()
+            .filter_map(|entry| {
+                let page = nodes.get(entry.page)?;
+                let subpages = page.subpages().count();
+
+                let item = match entry.subpage {
+                    Some(subpage) => format!(
+                        "  {} {}",
+                        if subpage + 1 == subpages {
+                            "└"
+                        } else {
+                            "├"
+                        },
+                        page.subpages().nth(subpage)?.name
+                    ),
+                    None if subpages == 0 => format!("./{}", page.name),
+                    None if self.expanded[entry.page] => format!("./{} ▾", page.name),
+                    None => format!("./{} ▸", page.name),
+                };
+                Some(ListItem::new(item))
+            })
+            .collect()
+    }
+
+    pub fn state_mut(&mut self) -> &mut ListState {
+        &mut self.state
+    }
+}
@@ -0,0 +1,38 @@
+//! Small deterministic noise for animations, and a generator for games.
+//!
+//! Nothing here needs to be unpredictable, it only needs to look busy, so
+//! a splitmix64 step is plenty.
+
+pub fn noise(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Noise for a cell of a grid at a given animation frame.
+pub fn noise2(x: usize, y: usize, frame: u64) -> u64 {
+    noise(noise(noise(x as u64) ^ y as u64) ^ frame)
+}
+
+/// A splitmix64 generator seeded from the wall clock, for games.
+#[derive(Debug)]
+pub struct Rng(u64);
+
+impl Default for Rng {
+    fn default() -> Self {
+        let seed = web_time::SystemTime::now()
+            .duration_since(web_time::UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_nanos() as u64);
+
+        Self(seed)
+    }
+}
+
+impl Rng {
+    /// A number in `0..bound`.
+    pub fn below(&mut self, bound: usize) -> usize {
+        self.0 = self.0.wrapping_add(1);
+        (noise(self.0) % bound.max(1) as u64) as usize
+    }
+}
@@ -0,0 +1,274 @@
+//! Raster images drawn with terminal cells.
+//!
+//! Pixels are decoded at build time (see `build.rs`) and scaled to the area
+//! at render time, as half-block cells with truecolor, as braille dots when
+//! detail matters more than color, or as grayscale ASCII for monochrome
+//! displays.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::Widget,
+};
+
+#[derive(Debug)]
+pub struct RasterImage {
+    pub name: &'static str,
+    pub width: usize,
+    pub height: usize,
+    /// RGBA, row by row.
+    pub pixels: &'static [u8],
+}
+
+include!(concat!(env!("OUT_DIR"), "/images.rs"));
+
+pub fn image(name: &str) -> Option<&'static RasterImage> {
+    IMAGES.iter().find(|image| image.name == name)
+}
+
+/// Whether the display can only show shades of a single color, as told by
+/// the `monochrome` media feature.
+pub fn prefers_monochrome() -> bool {
+    web_sys::window()
+        .and_then(|window| window.match_media("(monochrome)").ok().flatten())
+        .is_some_and(|query| query.matches())
+}
+
+const ASCII_RAMP: &[u8] = b" .:-=+*#%@";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RasterMode {
+    /// `▀` cells, two truecolor pixels per cell.
+    HalfBlock,
+    /// Braille cells, eight dots per cell in a single color.
+    Braille,
+    /// Grayscale characters, one pixel per cell.
+    Ascii,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Pixel {
+    rgb: [f32; 3],
+    alpha: f32,
+}
+
+impl Pixel {
+    fn luminance(self) -> f32 {
+        (0.2126 * self.rgb[0] + 0.7152 * self.rgb[1] + 0.0722 * self.rgb[2]) / 255.0
+    }
+
+    fn color(self) -> Color {
+        Color::Rgb(self.rgb[0] as u8, self.rgb[1] as u8, self.rgb[2] as u8)
+    }
+
+    fn is_visible(self) -> bool {
+        self.alpha >= 0.5
+    }
+}
+
+#[derive(Debug)]
+pub struct Raster {
+    image: &'static RasterImage,
+    mode: RasterMode,
+}
+
+impl Raster {
+    pub fn new(image: &'static RasterImage) -> Self {
+        Self {
+            image,
+            mode: RasterMode::HalfBlock,
+        }
+    }
+
+    pub fn mode(mut self, mode: RasterMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Picks grayscale ASCII on monochrome displays, braille when the
+    /// image has to shrink a lot to fit `area`, and half blocks otherwise.
+    pub fn auto_mode(self, area: Rect, monochrome: bool) -> Self {
+        let (columns, rows) = Raster::dots(area, RasterMode::HalfBlock);
+        let shrinks_a_lot = self.image.width > columns * 2 || self.image.height > rows * 2;
+
+        let mode = if monochrome {
+            RasterMode::Ascii
+        } else if shrinks_a_lot {
+            RasterMode::Braille
+        } else {
+            RasterMode::HalfBlock
+        };
+
+        self.mode(mode)
+    }
+
+    /// Horizontal and vertical pixel density of a cell in the given mode.
+    /// Cells are about twice as tall as wide, so every mode but ASCII ends
+    /// up with square pixels.
+    const fn density(mode: RasterMode) -> (usize, usize) {
+        match mode {
+            RasterMode::HalfBlock => (1, 2),
+            RasterMode::Braille => (2, 4),
+            RasterMode::Ascii => (1, 1),
+        }
+    }
+
+    fn dots(area: Rect, mode: RasterMode) -> (usize, usize) {
+        let (x, y) = Raster::density(mode);
+        (usize::from(area.width) * x, usize::from(area.height) * y)
+    }
+
+    /// The size the image is drawn at in `area`, in pixels of the mode,
+    /// keeping its aspect ratio.
+    fn fit(&self, area: Rect) -> (usize, usize) {
+        let (columns, rows) = Raster::dots(area, self.mode);
+        // ASCII cells are twice as tall as wide, so the image is squashed.
+        let aspect = if self.mode == RasterMode::Ascii {
+            2.0
+        } else {
+            1.0
+        };
+
+        let width = self.image.width as f32;
+        let height = self.image.height as f32 / aspect;
+        let scale = (columns as f32 / width).min(rows as f32 / height);
+
+        (
+            ((width * scale) as usize).clamp(1, columns.max(1)),
+            ((height * scale) as usize).clamp(1, rows.max(1)),
+        )
+    }
+
+    /// Averages the source pixels covered by the target pixel `(x, y)` of
+    /// an image scaled to `size`.
+    fn sample(&self, size: (usize, usize), x: usize, y: usize) -> Pixel {
+        let image = self.image;
+        let x0 = x * image.width / size.0;
+        let y0 = y * image.height / size.1;
+        let x1 = ((x + 1) * image.width / size.0)
+            .max(x0 + 1)
+            .min(image.width);
+        let y1 = ((y + 1) * image.height / size.1)
+            .max(y0 + 1)
+            .min(image.height);
+
+        let mut rgb = [0.0; 3];
+        let mut alpha = 0.0;
+        let mut count = 0.0;
+
+        for source_y in y0..y1 {
+            for source_x in x0..x1 {
+                let offset = (source_y * image.width + source_x) * 4;
+                let pixel = &image.pixels[offset..offset + 4];
+                let weight = f32::from(pixel[3]) / 255.0;
+
+                for (channel, value) in rgb.iter_mut().zip(pixel) {
+                    *channel += f32::from(*value) * weight;
+                }
+                alpha += weight;
+                count += 1.0;
+            }
+        }
+
+        if alpha > 0.0 {
+            rgb.iter_mut().for_each(|channel| *channel /= alpha);
+        }
+
+        Pixel {
+            rgb,
+            alpha: alpha / f32::max(count, 1.0),
+        }
+    }
+}
+
+impl Widget for Raster {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() || self.image.width == 0 || self.image.height == 0 {
+            return;
+        }
+
+        let size = self.fit(area);
+        let (density_x, density_y) = Raster::density(self.mode);
+        let columns = size.0.div_ceil(density_x);
+        let rows = size.1.div_ceil(density_y);
+
+        // Centered within the area.
+        let left = area.x + (area.width - columns as u16) / 2;
+        let top = area.y + (area.height - rows as u16) / 2;
+
+        let pixel = |x: usize, y: usize| {
+            (x < size.0 && y < size.1)
+                .then(|| self.sample(size, x, y))
+                .filter(|pixel| pixel.is_visible())
+        };
+
+        for row in 0..rows {
+            for column in 0..columns {
+                let Some(cell) = buf.cell_mut((left + column as u16, top + row as u16)) else {
+                    continue;
+                };
+
+                match self.mode {
+                    RasterMode::HalfBlock => {
+                        match (pixel(column, row * 2), pixel(column, row * 2 + 1)) {
+                            (Some(upper), Some(lower)) => {
+                                cell.set_char('▀')
+                                    .set_fg(upper.color())
+                                    .set_bg(lower.color());
+                            }
+                            (Some(upper), None) => {
+                                cell.set_char('▀').set_fg(upper.color());
+                            }
+                            (None, Some(lower)) => {
+                                cell.set_char('▄').set_fg(lower.color());
+                            }
+                            (None, None) => {}
+                        }
+                    }
+                    RasterMode::Braille => {
+                        const DOTS: [[u32; 4]; 2] =
+                            [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+                        let mut bits = 0;
+                        let mut rgb = [0.0; 3];
+                        let mut lit = 0.0;
+
+                        for (dx, column_dots) in DOTS.iter().enumerate() {
+                            for (dy, dot) in column_dots.iter().enumerate() {
+                                let Some(sample) = pixel(column * 2 + dx, row * 4 + dy) else {
+                                    continue;
+                                };
+
+                                if sample.luminance() > 0.1 {
+                                    bits |= dot;
+                                    rgb.iter_mut()
+                                        .zip(sample.rgb)
+                                        .for_each(|(sum, value)| *sum += value);
+                                    lit += 1.0;
+                                }
+                            }
+                        }
+
+                        if bits != 0 {
+                            let average = Pixel {
+                                rgb: rgb.map(|sum| sum / lit),
+                                alpha: 1.0,
+                            };
+                            let glyph = char::from_u32(0x2800 + bits).unwrap_or(' ');
+                            cell.set_char(glyph).set_fg(average.color());
+                        }
+                    }
+                    RasterMode::Ascii => {
+                        if let Some(sample) = pixel(column, row) {
+                            let index = (sample.luminance() * (ASCII_RAMP.len() - 1) as f32).round()
+                                as usize;
+                            cell.set_char(char::from(ASCII_RAMP[index]))
+                                .set_style(Style::reset());
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,480 @@
+//! The reader of the book pages.
+//!
+//! Books live under `assets/books`, one directory per page of the same
+//! name, and are converted chapter by chapter at build time (see
+//! `build.rs`). The reader opens on the table of contents, below the prose
+//! of the page, and a chapter takes the whole pane with the page and how
+//! far into the book it is at the bottom. The reader only takes keys once
+//! it has the focus, so the prose above scrolls as any other page until
+//! then. Bookmarks and the last position
+//! read are kept in storage, as is having gone past a content warning,
+//! which books that carry one show before anything else.
+
+use std::cell::{Cell, RefCell};
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState, Paragraph, StatefulWidget, Widget, Wrap},
+};
+
+use crate::{
+    document::Document,
+    focus::Reply,
+    keymap::{Action, Context, Preset},
+    storage,
+};
+
+#[derive(Debug)]
+pub struct Book {
+    /// Name of the directory under `assets/books`, and of the page the
+    /// book belongs to.
+    pub id: &'static str,
+    pub title: &'static str,
+    pub warning: Option<&'static str>,
+    pub chapters: &'static [Chapter],
+}
+
+#[derive(Debug)]
+pub struct Chapter {
+    pub title: &'static str,
+    /// Page markup, starting with the title as a heading.
+    pub text: &'static str,
+}
+
+include!(concat!(env!("OUT_DIR"), "/books.rs"));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Position {
+    chapter: usize,
+    /// First row of the chapter on screen.
+    row: u16,
+}
+
+impl Position {
+    const START: Position = Position { chapter: 0, row: 0 };
+
+    fn parse(saved: &str) -> Option<Position> {
+        let (chapter, row) = saved.split_once(':')?;
+
+        Some(Position {
+            chapter: chapter.parse().ok()?,
+            row: row.parse().ok()?,
+        })
+    }
+
+    fn save(self) -> String {
+        format!("{}:{}", self.chapter, self.row)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum View {
+    Warning,
+    Contents,
+    Chapter,
+}
+
+#[derive(Debug)]
+pub struct Reader {
+    book: &'static Book,
+    view: View,
+    position: Position,
+    bookmarks: Vec<Position>,
+    /// Rows of the table of contents: the chapters, then the bookmarks.
+    contents: ListState,
+    /// The width chapters were last laid out at, and how many rows each
+    /// took, for paging and the progress shown.
+    layout: RefCell<(u16, Vec<u16>)>,
+    /// Height of the chapter pane at the last render.
+    page_height: Cell<u16>,
+}
+
+impl Reader {
+    pub fn new(book: &'static Book) -> Self {
+        let chapters = book.chapters.len();
+        let position = storage::get(&format!("reader.{}.position", book.id))
+            .and_then(|saved| Position::parse(&saved))
+            .filter(|position| position.chapter < chapters)
+            .unwrap_or(Position::START);
+        let bookmarks = storage::get(&format!("reader.{}.bookmarks", book.id))
+            .map(|saved| {
+                saved
+                    .split(',')
+                    .filter_map(Position::parse)
+                    .filter(|bookmark| bookmark.chapter < chapters)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let warned =
+            storage::get(&format!("reader.{}.warning", book.id)).as_deref() == Some("read");
+
+        Self {
+            book,
+            view: match book.warning {
+                Some(_) if !warned => View::Warning,
+                _ => View::Contents,
+            },
+            position,
+            bookmarks,
+            contents: ListState::default().with_selected(Some(position.chapter)),
+            layout: RefCell::new((0, Vec::new())),
+            page_height: Cell::new(0),
+        }
+    }
+
+    pub fn id(&self) -> &'static str {
+        self.book.id
+    }
+
+    pub fn title(&self) -> &'static str {
+        self.book.title
+    }
+
+    /// Whether a chapter is open, which takes the whole pane.
+    pub fn is_reading(&self) -> bool {
+        self.view == View::Chapter
+    }
+
+    /// Acts on an action of the content pane while the reader has the
+    /// focus, `times` over. Chapters scroll with the page motions and turn
+    /// with the sideways ones.
+    pub fn handle(&mut self, action: Action, times: u16) -> Reply {
+        match self.view {
+            View::Warning => self.handle_warning(action),
+            View::Contents => self.handle_contents(action, times),
+            View::Chapter => self.handle_chapter(action, times),
+        }
+    }
+
+    fn handle_warning(&mut self, action: Action) -> Reply {
+        match action {
+            Action::Select => {
+                storage::set(&format!("reader.{}.warning", self.book.id), "read");
+                self.view = View::Contents;
+                Reply::Ran
+            }
+            _ => Reply::Ignored,
+        }
+    }
+
+    fn handle_contents(&mut self, action: Action, times: u16) -> Reply {
+        let rows = self.book.chapters.len() + self.bookmarks.len();
+        let selected = self.contents.selected().unwrap_or_default();
+        let times = usize::from(times);
+
+        match action {
+            Action::ScrollUp => self.contents.select(Some(selected.saturating_sub(times))),
+            Action::ScrollDown => self
+                .contents
+                .select(Some((selected + times).min(rows.saturating_sub(1)))),
+            Action::Select if rows > 0 => {
+                let chapters = self.book.chapters.len();
+
+                if selected >= chapters {
+                    self.position = self.bookmarks[selected - chapters];
+                } else if selected != self.position.chapter {
+                    // The chapter read last opens where it was left.
+                    self.position = Position {
+                        chapter: selected,
+                        row: 0,
+                    };
+                }
+
+                self.view = View::Chapter;
+                self.save_position();
+                return Reply::Ran;
+            }
+            // Bookmarks are made in a chapter and deleted from the contents.
+            Action::Bookmark if selected >= self.book.chapters.len() => {
+                self.bookmarks.remove(selected - self.book.chapters.len());
+                self.contents
+                    .select(Some(selected.min(rows.saturating_sub(2))));
+                self.save_bookmarks();
+            }
+            _ => return Reply::Ignored,
+        }
+
+        Reply::Consumed
+    }
+
+    fn handle_chapter(&mut self, action: Action, times: u16) -> Reply {
+        let page = self.page_height.get().saturating_sub(1).max(1);
+        let half_page = (page / 2).max(1);
+        let row = self.position.row;
+        let chapter = self.position.chapter;
+
+        match action {
+            Action::ScrollUp => self.scroll_to(row.saturating_sub(times)),
+            Action::ScrollDown => self.scroll_to(row.saturating_add(times)),
+            Action::HalfPageUp => {
+                self.scroll_to(row.saturating_sub(half_page.saturating_mul(times)))
+            }
+            Action::HalfPageDown => {
+                self.scroll_to(row.saturating_add(half_page.saturating_mul(times)))
+            }
+            Action::PageUp => self.scroll_to(row.saturating_sub(page.saturating_mul(times))),
+            Action::PageDown => self.scroll_to(row.saturating_add(page.saturating_mul(times))),
+            Action::Top => self.scroll_to(0),
+            Action::Bottom => self.scroll_to(u16::MAX),
+            Action::ScrollLeft if chapter > 0 => {
+                self.open(chapter.saturating_sub(usize::from(times)))
+            }
+            Action::ScrollRight if chapter + 1 < self.book.chapters.len() => {
+                self.open((chapter + usize::from(times)).min(self.book.chapters.len() - 1))
+            }
+            Action::Bookmark => self.toggle_bookmark(),
+            Action::Contents => {
+                self.view = View::Contents;
+                self.contents.select(Some(chapter));
+                return Reply::Ran;
+            }
+            _ => return Reply::Ignored,
+        }
+
+        Reply::Consumed
+    }
+
+    fn open(&mut self, chapter: usize) {
+        self.position = Position { chapter, row: 0 };
+        self.save_position();
+    }
+
+    fn scroll_to(&mut self, row: u16) {
+        let rows = self.chapter_rows(self.position.chapter);
+        let bottom = rows.saturating_sub(self.page_height.get());
+
+        self.position.row = row.min(bottom);
+        self.save_position();
+    }
+
+    /// Rows the chapter took at the last render, or as many as anyone
+    /// could ask for before the first.
+    fn chapter_rows(&self, chapter: usize) -> u16 {
+        match &*self.layout.borrow() {
+            (0, _) => u16::MAX,
+            (_, rows) => rows.get(chapter).copied().unwrap_or_default(),
+        }
+    }
+
+    fn toggle_bookmark(&mut self) {
+        let chapter = self.position.chapter;
+        let page = self.page_height.get().max(1);
+        let same_page = |bookmark: &Position| {
+            bookmark.chapter == chapter && bookmark.row / page == self.position.row / page
+        };
+
+        match self.bookmarks.iter().position(same_page) {
+            Some(index) => {
+                self.bookmarks.remove(index);
+            }
+            None => {
+                self.bookmarks.push(self.position);
+                self.bookmarks
+                    .sort_by_key(|bookmark| (bookmark.chapter, bookmark.row));
+            }
+        }
+        self.save_bookmarks();
+    }
+
+    fn is_bookmarked(&self) -> bool {
+        let page = self.page_height.get().max(1);
+
+        self.bookmarks.iter().any(|bookmark| {
+            bookmark.chapter == self.position.chapter
+                && bookmark.row / page == self.position.row / page
+        })
+    }
+
+    fn save_position(&self) {
+        storage::set(
+            &format!("reader.{}.position", self.book.id),
+            &self.position.save(),
+        );
+    }
+
+    fn save_bookmarks(&self) {
+        let saved: Vec<String> = self
+            .bookmarks
+            .iter()
+            .map(|bookmark| bookmark.save())
+            .collect();
+        storage::set(
+            &format!("reader.{}.bookmarks", self.book.id),
+            &saved.join(","),
+        );
+    }
+
+    /// Lays the chapters out again when the width changed since the last
+    /// time.
+    fn lay_out(&self, width: u16) {
+        let mut layout = self.layout.borrow_mut();
+
+        if layout.0 != width {
+            *layout = (
+                width,
+                self.book
+                    .chapters
+                    .iter()
+                    .map(|chapter| Document::parse(chapter.text).height(width))
+                    .collect(),
+            );
+        }
+    }
+
+    /// How far into the book a position is, counting up to the last row on
+    /// screen, in percent.
+    fn progress(&self, position: Position) -> u32 {
+        let layout = self.layout.borrow();
+        let total: u32 = layout.1.iter().map(|rows| u32::from(*rows)).sum();
+        let before: u32 = layout
+            .1
+            .iter()
+            .take(position.chapter)
+            .map(|rows| u32::from(*rows))
+            .sum();
+        let rows = u32::from(layout.1.get(position.chapter).copied().unwrap_or_default());
+        let seen = (u32::from(position.row) + u32::from(self.page_height.get())).min(rows);
+
+        (before + seen) * 100 / total.max(1)
+    }
+
+    fn render_warning(&self, area: Rect, buf: &mut Buffer, keymap: Preset) {
+        let lines = vec![
+            Line::from("Content warning").bold().light_red(),
+            Line::default(),
+            Line::from(self.book.warning.unwrap_or_default()),
+            Line::default(),
+            Line::from(format!(
+                "Press {} to read on, or leave the page.",
+                keymap.keys(Context::Content, Action::Select)
+            ))
+            .dark_gray(),
+        ];
+        let paragraph = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        let height = paragraph.line_count(area.width) as u16;
+        let [_, area, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(height),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+
+        paragraph.render(area, buf);
+    }
+
+    fn render_contents(&self, area: Rect, buf: &mut Buffer, keymap: Preset) {
+        let [list_area, status_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+        let chapters = self
+            .book
+            .chapters
+            .iter()
+            .enumerate()
+            .map(|(index, chapter)| {
+                let marker = if index == self.position.chapter {
+                    "●"
+                } else {
+                    " "
+                };
+                ListItem::new(Line::from(vec![
+                    Span::from(format!("{marker} {:>2}  ", index + 1)).dark_gray(),
+                    Span::from(chapter.title),
+                ]))
+            });
+        let bookmarks = self.bookmarks.iter().map(|bookmark| {
+            let title = self.book.chapters[bookmark.chapter].title;
+            ListItem::new(Line::from(vec![
+                Span::from("★ ").light_yellow(),
+                Span::from(format!("{title}, {}%", self.progress(*bookmark))),
+            ]))
+        });
+
+        let mut state = self.contents.clone();
+        StatefulWidget::render(
+            List::new(chapters.chain(bookmarks).collect::<Vec<_>>())
+                .highlight_style(Style::default().fg(Color::Black).bg(Color::LightMagenta)),
+            list_area,
+            buf,
+            &mut state,
+        );
+
+        let keys = |action| keymap.keys(Context::Content, action);
+        let status = if self.bookmarks.is_empty() {
+            format!(
+                "{} reads, {} in a chapter bookmarks it.",
+                keys(Action::Select),
+                keys(Action::Bookmark)
+            )
+        } else {
+            format!(
+                "{} reads, {} deletes a bookmark.",
+                keys(Action::Select),
+                keys(Action::Bookmark)
+            )
+        };
+        Line::from(format!("{status}  {}% read", self.progress(self.position)))
+            .dark_gray()
+            .render(status_area, buf);
+    }
+
+    fn render_chapter(&self, area: Rect, buf: &mut Buffer) {
+        let [text_area, status_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+        let chapter = &self.book.chapters[self.position.chapter];
+
+        self.page_height.set(text_area.height);
+        let rows = self.chapter_rows(self.position.chapter);
+        let row = self.position.row.min(rows.saturating_sub(text_area.height));
+
+        Document::parse(chapter.text).render(text_area, buf, row, 0);
+
+        let page = text_area.height.max(1);
+        let position = Position {
+            row,
+            ..self.position
+        };
+        let bookmark = if self.is_bookmarked() { "★ " } else { "" };
+        let status = Line::from(vec![
+            Span::from(bookmark).light_yellow(),
+            Span::from(format!(
+                "{}/{} {}",
+                position.chapter + 1,
+                self.book.chapters.len(),
+                chapter.title
+            )),
+            Span::from(format!(
+                "  page {}/{}  {}%",
+                // The page most of the screen is on.
+                ((row + page / 2) / page + 1)
+                    .min(rows.div_ceil(page))
+                    .max(1),
+                rows.div_ceil(page).max(1),
+                self.progress(position)
+            ))
+            .dark_gray(),
+        ]);
+        status.render(status_area, buf);
+    }
+
+    /// Draws the reader, naming the keys of `keymap` in its hints.
+    pub fn render(&self, area: Rect, buf: &mut Buffer, keymap: Preset) {
+        if area.is_empty() {
+            return;
+        }
+
+        // The status line takes a row, never a column, so chapters are laid
+        // out at the width of the pane.
+        self.lay_out(area.width);
+
+        match self.view {
+            View::Warning => self.render_warning(area, buf, keymap),
+            View::Contents => self.render_contents(area, buf, keymap),
+            View::Chapter => self.render_chapter(area, buf),
+        }
+    }
+}
@@ -0,0 +1,383 @@
+//! A read-eval-print loop for the language pages.
+//!
+//! Any language implementing [`Evaluator`], a built-in one or one compiled
+//! to wasm, gets an input line with history, entries spanning lines until
+//! the evaluator calls them complete, and a scrollback of results, errors
+//! and how long each entry took. Lines starting with `:` are for the REPL
+//! itself: `:examples` lists the snippets the evaluator ships and `:load`
+//! puts one in the input. [`Lisp`](lisp::Lisp) ships as the demo.
+
+pub mod lisp;
+pub mod lox;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Paragraph, Widget, Wrap},
+};
+use ratzilla::event::{KeyCode, KeyEvent};
+use web_time::{Duration, Instant};
+
+use crate::focus::Reply;
+
+const PROMPT: &str = "> ";
+const CONTINUATION: &str = ". ";
+const CURSOR: char = '█';
+/// Outputs kept in the scrollback before the oldest are dropped.
+const SCROLLBACK: usize = 200;
+
+pub trait Evaluator: std::fmt::Debug {
+    /// Name of the language, shown on the border of the REPL.
+    fn name(&self) -> &'static str;
+
+    /// Printed at the top of the scrollback.
+    fn banner(&self) -> &'static str;
+
+    /// Whether `source` is a whole entry, or Enter should open another
+    /// line for it.
+    fn is_complete(&self, _source: &str) -> bool {
+        true
+    }
+
+    /// Evaluates an entry, returning its result or why it failed.
+    fn eval(&mut self, source: &str) -> Result<String, String>;
+
+    /// What the last entry printed along the way, shown above its result
+    /// even when it failed.
+    fn take_output(&mut self) -> String {
+        String::new()
+    }
+
+    /// Ready-made snippets, by name.
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
+}
+
+#[derive(Debug)]
+enum Output {
+    Entry(String),
+    Result(String),
+    Error(String),
+    Timing(Duration),
+}
+
+#[derive(Debug)]
+pub struct Repl {
+    evaluator: Box<dyn Evaluator>,
+    scrollback: Vec<Output>,
+    /// The lines of the entry being typed, never empty.
+    input: Vec<String>,
+    history: Vec<String>,
+    /// The history entry shown in the input while browsing with the arrows.
+    recalled: Option<usize>,
+}
+
+impl Repl {
+    pub fn new(evaluator: Box<dyn Evaluator>) -> Self {
+        Self {
+            evaluator,
+            scrollback: Vec::new(),
+            input: vec![String::new()],
+            history: Vec::new(),
+            recalled: None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.evaluator.name()
+    }
+
+    fn set_input(&mut self, source: &str) {
+        self.input = source.split('\n').map(str::to_string).collect();
+    }
+
+    fn line_mut(&mut self) -> &mut String {
+        if self.input.is_empty() {
+            self.input.push(String::new());
+        }
+
+        let last = self.input.len() - 1;
+        &mut self.input[last]
+    }
+
+    pub fn handle(&mut self, event: &KeyEvent) -> Reply {
+        if event.ctrl || event.alt {
+            return Reply::Ignored;
+        }
+
+        match event.code {
+            KeyCode::Char(letter) => {
+                self.line_mut().push(letter);
+                self.recalled = None;
+            }
+            KeyCode::Tab => self.line_mut().push_str("  "),
+            // Backspace on an empty line goes back to the end of the last.
+            KeyCode::Backspace if self.input.len() > 1 && self.line_mut().is_empty() => {
+                self.input.pop();
+            }
+            KeyCode::Backspace => {
+                self.line_mut().pop();
+            }
+            KeyCode::Up if !self.history.is_empty() && self.input.len() == 1 => {
+                let index = self
+                    .recalled
+                    .map_or(self.history.len() - 1, |index| index.saturating_sub(1));
+                self.recalled = Some(index);
+                self.set_input(&self.history[index].clone());
+            }
+            KeyCode::Down => match self.recalled {
+                Some(index) if index + 1 < self.history.len() => {
+                    self.recalled = Some(index + 1);
+                    self.set_input(&self.history[index + 1].clone());
+                }
+                Some(_) => {
+                    self.recalled = None;
+                    self.set_input("");
+                }
+                None => return Reply::Ignored,
+            },
+            KeyCode::Enter => {
+                let source = self.input.join("\n");
+
+                if source.trim().is_empty() {
+                    return Reply::Consumed;
+                }
+
+                // Commands are for the REPL, whatever the language makes
+                // of them.
+                let command = self.input.len() == 1 && source.trim().starts_with(':');
+                if !command && !self.evaluator.is_complete(&source) {
+                    self.input.push(String::new());
+                    return Reply::Consumed;
+                }
+
+                self.recalled = None;
+                self.set_input("");
+                if self.history.last() != Some(&source) {
+                    self.history.push(source.clone());
+                }
+
+                match source.trim().strip_prefix(':') {
+                    Some(command) => self.command(source.clone(), command),
+                    None => self.run(source),
+                }
+                return Reply::Ran;
+            }
+            _ => return Reply::Ignored,
+        }
+
+        Reply::Consumed
+    }
+
+    fn run(&mut self, source: String) {
+        let started_at = Instant::now();
+        let result = self.evaluator.eval(&source);
+        let elapsed = started_at.elapsed();
+
+        self.scrollback.push(Output::Entry(source));
+        self.scrollback
+            .push(Output::Result(self.evaluator.take_output()));
+        self.scrollback.push(match result {
+            Ok(result) => Output::Result(result),
+            Err(error) => Output::Error(error),
+        });
+        self.scrollback.push(Output::Timing(elapsed));
+        self.trim_scrollback();
+    }
+
+    fn command(&mut self, source: String, command: &str) {
+        let examples = self.evaluator.examples();
+        let mut words = command.split_whitespace();
+
+        let output = match (words.next(), words.next()) {
+            (Some("examples"), _) if examples.is_empty() => {
+                Output::Result("No examples for this language.".into())
+            }
+            (Some("examples"), _) => {
+                let names: Vec<&str> = examples.iter().map(|(name, _)| *name).collect();
+                Output::Result(format!(
+                    "{}\nType :load <name> to edit one.",
+                    names.join("  ")
+                ))
+            }
+            (Some("load"), Some(name)) => {
+                match examples.iter().find(|(example, _)| *example == name) {
+                    Some((_, example)) => {
+                        self.set_input(example.trim_end());
+                        return;
+                    }
+                    None => Output::Error(format!("No example named {name}. Try :examples.")),
+                }
+            }
+            (Some("load"), None) => Output::Error("Which one? Try :examples.".into()),
+            _ => Output::Error(format!("Unknown command :{command}. Try :examples.")),
+        };
+
+        self.scrollback.push(Output::Entry(source));
+        self.scrollback.push(output);
+        self.trim_scrollback();
+    }
+
+    fn trim_scrollback(&mut self) {
+        if self.scrollback.len() > SCROLLBACK {
+            self.scrollback.drain(..self.scrollback.len() - SCROLLBACK);
+        }
+    }
+
+    fn text(&self) -> Text<'static> {
+        let mut lines: Vec<Line> = self
+            .evaluator
+            .banner()
+            .lines()
+            .map(|line| Line::from(line.to_string()).dark_gray())
+            .collect();
+
+        let entry = |source: &str| -> Vec<Line<'static>> {
+            source
+                .split('\n')
+                .enumerate()
+                .map(|(index, line)| {
+                    Line::from(vec![
+                        Span::from(if index == 0 { PROMPT } else { CONTINUATION }).light_magenta(),
+                        Span::from(line.to_string()).light_cyan(),
+                    ])
+                })
+                .collect()
+        };
+
+        for output in &self.scrollback {
+            match output {
+                Output::Entry(source) => lines.extend(entry(source)),
+                Output::Result(result) => lines.extend(
+                    result
+                        .lines()
+                        .map(|line| Line::from(line.to_string()).fg(Color::White)),
+                ),
+                Output::Error(error) => lines.extend(
+                    error
+                        .lines()
+                        .map(|line| Line::from(format!("error: {line}")).light_red()),
+                ),
+                Output::Timing(elapsed) => lines.push(
+                    Line::from(format!("({:.2} ms)", elapsed.as_secs_f64() * 1000.0)).dark_gray(),
+                ),
+            }
+        }
+
+        let mut input = entry(&self.input.join("\n"));
+        if let Some(line) = input.last_mut() {
+            line.push_span(Span::from(CURSOR.to_string()).light_cyan());
+        }
+        lines.extend(input);
+
+        Text::from(lines)
+    }
+}
+
+/// The scrollback always shows its newest lines, with the input at the
+/// bottom.
+impl Widget for &Repl {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let paragraph = Paragraph::new(self.text()).wrap(Wrap { trim: false });
+        let height = paragraph.line_count(area.width) as u16;
+
+        paragraph
+            .scroll((height.saturating_sub(area.height), 0))
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{repl::lox::Lox, text};
+
+    fn press(repl: &mut Repl, code: KeyCode) -> Reply {
+        repl.handle(&KeyEvent {
+            code,
+            ctrl: false,
+            alt: false,
+            shift: false,
+        })
+    }
+
+    fn enter(repl: &mut Repl, source: &str) -> Reply {
+        for letter in source.chars() {
+            press(repl, KeyCode::Char(letter));
+        }
+        press(repl, KeyCode::Enter)
+    }
+
+    fn lox() -> Repl {
+        Repl::new(Box::new(Lox::default()))
+    }
+
+    #[test]
+    fn the_arrows_walk_the_history() {
+        let mut repl = lox();
+        enter(&mut repl, "print 1;");
+        enter(&mut repl, "print 2;");
+        enter(&mut repl, "print 2;");
+
+        assert_eq!(repl.history, ["print 1;", "print 2;"]);
+
+        press(&mut repl, KeyCode::Up);
+        press(&mut repl, KeyCode::Up);
+        press(&mut repl, KeyCode::Up);
+        assert_eq!(repl.input, ["print 1;"]);
+
+        press(&mut repl, KeyCode::Down);
+        assert_eq!(repl.input, ["print 2;"]);
+
+        press(&mut repl, KeyCode::Down);
+        assert_eq!(repl.input, [""]);
+        assert_eq!(press(&mut repl, KeyCode::Down), Reply::Ignored);
+    }
+
+    #[test]
+    fn unfinished_entries_open_another_line() {
+        let mut repl = lox();
+
+        assert_eq!(enter(&mut repl, "fun f() {"), Reply::Consumed);
+        assert_eq!(enter(&mut repl, "}"), Reply::Ran);
+        assert_eq!(repl.history, ["fun f() {\n}"]);
+        assert_eq!(repl.input, [""]);
+    }
+
+    #[test]
+    fn load_puts_an_example_in_the_input() {
+        let mut repl = lox();
+        let (name, source) = text::LOX_EXAMPLES[0];
+
+        assert_eq!(enter(&mut repl, &format!(":load {name}")), Reply::Ran);
+        assert_eq!(repl.input.join("\n"), source.trim_end());
+
+        enter(&mut repl, "");
+        assert!(matches!(repl.scrollback.last(), Some(Output::Timing(_))));
+    }
+
+    #[test]
+    fn unknown_examples_and_commands_are_errors() {
+        let mut repl = lox();
+
+        enter(&mut repl, ":load nothing");
+        enter(&mut repl, ":frobnicate");
+        enter(&mut repl, ":examples");
+
+        let outputs: Vec<String> = repl
+            .scrollback
+            .iter()
+            .filter_map(|output| match output {
+                Output::Result(text) | Output::Error(text) => Some(text.clone()),
+                _ => None,
+            })
+            .collect();
+
+        assert_eq!(outputs[0], "No example named nothing. Try :examples.");
+        assert_eq!(outputs[1], "Unknown command :frobnicate. Try :examples.");
+        assert!(outputs[2].starts_with(text::LOX_EXAMPLES[0].0));
+    }
+}
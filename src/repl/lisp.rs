@@ -0,0 +1,537 @@
+//! A pocket Lisp, the demo [`Evaluator`].
+//!
+//! Numbers, symbols, lists and closures, with `define`, `lambda`, `if`,
+//! `cond`, `let`, `begin`, `quote`, `and` and `or` as special forms and a
+//! handful of arithmetic and list builtins. Recursion is capped so that a
+//! runaway definition errors out instead of blowing the wasm stack, and
+//! every entry gets a budget of steps so that a merely slow one, such as
+//! a naive `(fib 40)`, errors out instead of freezing the page.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashMap,
+    fmt,
+    rc::Rc,
+};
+
+use super::Evaluator;
+use crate::text;
+
+/// Nested evaluations allowed before giving up.
+const MAX_DEPTH: usize = 256;
+/// Evaluations an entry may go through.
+const MAX_STEPS: usize = 1_000_000;
+
+type Builtin = fn(&[Value]) -> Result<Value, String>;
+
+#[derive(Clone)]
+enum Value {
+    Nil,
+    Bool(bool),
+    Number(f64),
+    Symbol(String),
+    List(Vec<Value>),
+    Lambda(Rc<Lambda>),
+    Builtin(&'static str, Builtin),
+}
+
+struct Lambda {
+    parameters: Vec<String>,
+    body: Vec<Value>,
+    scope: Rc<RefCell<Scope>>,
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Nil => write!(f, "()"),
+            Value::Bool(true) => write!(f, "#t"),
+            Value::Bool(false) => write!(f, "#f"),
+            Value::Number(number) if number.fract() == 0.0 && number.abs() < 1e15 => {
+                write!(f, "{}", *number as i64)
+            }
+            Value::Number(number) => write!(f, "{number}"),
+            Value::Symbol(symbol) => write!(f, "{symbol}"),
+            Value::List(items) => {
+                let items: Vec<String> = items.iter().map(Value::to_string).collect();
+                write!(f, "({})", items.join(" "))
+            }
+            Value::Lambda(_) => write!(f, "#<lambda>"),
+            Value::Builtin(name, _) => write!(f, "#<builtin {name}>"),
+        }
+    }
+}
+
+impl Value {
+    fn is_truthy(&self) -> bool {
+        !matches!(self, Value::Bool(false) | Value::Nil)
+    }
+
+    fn number(&self) -> Result<f64, String> {
+        match self {
+            Value::Number(number) => Ok(*number),
+            other => Err(format!("expected a number, got {other}")),
+        }
+    }
+
+    fn list(&self) -> Result<&[Value], String> {
+        match self {
+            Value::List(items) => Ok(items),
+            Value::Nil => Ok(&[]),
+            other => Err(format!("expected a list, got {other}")),
+        }
+    }
+
+    fn from_list(items: Vec<Value>) -> Value {
+        if items.is_empty() {
+            Value::Nil
+        } else {
+            Value::List(items)
+        }
+    }
+}
+
+#[derive(Default)]
+struct Scope {
+    bindings: HashMap<String, Value>,
+    parent: Option<Rc<RefCell<Scope>>>,
+}
+
+impl Scope {
+    fn child(parent: &Rc<RefCell<Scope>>) -> Rc<RefCell<Scope>> {
+        Rc::new(RefCell::new(Scope {
+            bindings: HashMap::new(),
+            parent: Some(parent.clone()),
+        }))
+    }
+
+    fn get(&self, name: &str) -> Option<Value> {
+        match self.bindings.get(name) {
+            Some(value) => Some(value.clone()),
+            None => self.parent.as_ref()?.borrow().get(name),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+
+    for line in source.lines() {
+        let code = line.split(';').next().unwrap_or_default();
+        let spaced = code
+            .replace('(', " ( ")
+            .replace(')', " ) ")
+            .replace('\'', " ' ");
+
+        tokens.extend(spaced.split_whitespace().map(str::to_string));
+    }
+    tokens
+}
+
+fn parse(tokens: &[String], position: &mut usize) -> Result<Value, String> {
+    let token = tokens.get(*position).ok_or("unexpected end of input")?;
+    *position += 1;
+
+    match token.as_str() {
+        "(" => {
+            let mut items = Vec::new();
+
+            while tokens.get(*position).map(String::as_str) != Some(")") {
+                if *position >= tokens.len() {
+                    return Err("missing )".into());
+                }
+                items.push(parse(tokens, position)?);
+            }
+            *position += 1;
+
+            Ok(Value::from_list(items))
+        }
+        ")" => Err("unexpected )".into()),
+        "'" => Ok(Value::List(vec![
+            Value::Symbol("quote".into()),
+            parse(tokens, position)?,
+        ])),
+        "#t" => Ok(Value::Bool(true)),
+        "#f" => Ok(Value::Bool(false)),
+        atom => Ok(atom
+            .parse()
+            .map(Value::Number)
+            .unwrap_or_else(|_| Value::Symbol(atom.to_string()))),
+    }
+}
+
+fn arithmetic(name: &str, arguments: &[Value]) -> Result<Value, String> {
+    let numbers = arguments
+        .iter()
+        .map(Value::number)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let result = match (name, numbers.as_slice()) {
+        ("+", numbers) => numbers.iter().sum(),
+        ("*", numbers) => numbers.iter().product(),
+        ("-", [number]) => -number,
+        ("/", [number]) => 1.0 / number,
+        (_, []) => return Err(format!("{name} needs at least one argument")),
+        ("/", [_, rest @ ..]) if rest.contains(&0.0) => return Err("division by zero".into()),
+        (_, [first, rest @ ..]) => rest.iter().fold(*first, |result, number| match name {
+            "-" => result - number,
+            "/" => result / number,
+            _ => result % number,
+        }),
+    };
+    Ok(Value::Number(result))
+}
+
+fn compare(name: &str, arguments: &[Value]) -> Result<Value, String> {
+    let numbers = arguments
+        .iter()
+        .map(Value::number)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let holds = numbers.windows(2).all(|pair| match name {
+        "<" => pair[0] < pair[1],
+        ">" => pair[0] > pair[1],
+        "<=" => pair[0] <= pair[1],
+        ">=" => pair[0] >= pair[1],
+        _ => pair[0] == pair[1],
+    });
+    Ok(Value::Bool(holds))
+}
+
+fn one(name: &str, arguments: &[Value]) -> Result<Value, String> {
+    match arguments {
+        [argument] => Ok(argument.clone()),
+        _ => Err(format!(
+            "{name} takes one argument, got {}",
+            arguments.len()
+        )),
+    }
+}
+
+#[rustfmt::skip]
+const BUILTINS: &[(&str, Builtin)] = &[
+    ("+", |arguments| arithmetic("+", arguments)),
+    ("-", |arguments| arithmetic("-", arguments)),
+    ("*", |arguments| arithmetic("*", arguments)),
+    ("/", |arguments| arithmetic("/", arguments)),
+    ("mod", |arguments| arithmetic("mod", arguments)),
+    ("<", |arguments| compare("<", arguments)),
+    (">", |arguments| compare(">", arguments)),
+    ("<=", |arguments| compare("<=", arguments)),
+    (">=", |arguments| compare(">=", arguments)),
+    ("=", |arguments| compare("=", arguments)),
+    ("not", |arguments| Ok(Value::Bool(!one("not", arguments)?.is_truthy()))),
+    ("list", |arguments| Ok(Value::from_list(arguments.to_vec()))),
+    ("cons", |arguments| match arguments {
+        [head, tail] => {
+            let mut items = vec![head.clone()];
+            items.extend_from_slice(tail.list()?);
+            Ok(Value::List(items))
+        }
+        _ => Err("cons takes two arguments".into()),
+    }),
+    ("car", |arguments| {
+        one("car", arguments)?.list()?.first().cloned().ok_or("car of an empty list".into())
+    }),
+    ("cdr", |arguments| match one("cdr", arguments)?.list()? {
+        [] => Err("cdr of an empty list".into()),
+        [_, rest @ ..] => Ok(Value::from_list(rest.to_vec())),
+    }),
+    ("null?", |arguments| Ok(Value::Bool(matches!(one("null?", arguments)?, Value::Nil)))),
+    ("length", |arguments| Ok(Value::Number(one("length", arguments)?.list()?.len() as f64))),
+];
+
+#[derive(Clone)]
+pub struct Lisp {
+    global: Rc<RefCell<Scope>>,
+    /// Evaluations of the running entry so far.
+    steps: Cell<usize>,
+}
+
+impl fmt::Debug for Lisp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Lisp").finish_non_exhaustive()
+    }
+}
+
+impl Default for Lisp {
+    fn default() -> Self {
+        let bindings = BUILTINS
+            .iter()
+            .map(|(name, builtin)| (name.to_string(), Value::Builtin(name, *builtin)))
+            .collect();
+
+        Self {
+            global: Rc::new(RefCell::new(Scope {
+                bindings,
+                parent: None,
+            })),
+            steps: Cell::new(0),
+        }
+    }
+}
+
+impl Lisp {
+    fn evaluate(
+        &self,
+        expression: &Value,
+        scope: &Rc<RefCell<Scope>>,
+        depth: usize,
+    ) -> Result<Value, String> {
+        if depth > MAX_DEPTH {
+            return Err("recursion too deep".into());
+        }
+
+        self.steps.set(self.steps.get() + 1);
+        if self.steps.get() > MAX_STEPS {
+            return Err(format!(
+                "gave up after {MAX_STEPS} steps, is something looping forever?"
+            ));
+        }
+
+        let items = match expression {
+            Value::Symbol(name) => {
+                return scope
+                    .borrow()
+                    .get(name)
+                    .ok_or_else(|| format!("unbound symbol: {name}"))
+            }
+            Value::List(items) => items,
+            other => return Ok(other.clone()),
+        };
+
+        let [head, operands @ ..] = items.as_slice() else {
+            return Ok(Value::Nil);
+        };
+
+        let eval = |expression: &Value| self.evaluate(expression, scope, depth + 1);
+        let name = match head {
+            Value::Symbol(name) => name.as_str(),
+            _ => "",
+        };
+
+        match (name, operands) {
+            ("quote", [quoted]) => Ok(quoted.clone()),
+            ("if", [condition, then, otherwise @ ..]) => {
+                if eval(condition)?.is_truthy() {
+                    eval(then)
+                } else {
+                    otherwise.first().map_or(Ok(Value::Nil), eval)
+                }
+            }
+            ("cond", clauses) => {
+                for clause in clauses {
+                    let [condition, body @ ..] = clause.list()? else {
+                        return Err("empty cond clause".into());
+                    };
+
+                    let otherwise = matches!(condition, Value::Symbol(name) if name == "else");
+                    if otherwise || eval(condition)?.is_truthy() {
+                        return self.sequence(body, scope, depth);
+                    }
+                }
+                Ok(Value::Nil)
+            }
+            ("define", [Value::Symbol(name), value]) => {
+                let value = eval(value)?;
+                scope.borrow_mut().bindings.insert(name.clone(), value);
+                Ok(Value::Symbol(name.clone()))
+            }
+            ("define", [Value::List(signature), body @ ..]) => {
+                let [Value::Symbol(name), parameters @ ..] = signature.as_slice() else {
+                    return Err("define needs a name".into());
+                };
+
+                let lambda = Lisp::lambda(parameters, body, scope)?;
+                scope.borrow_mut().bindings.insert(name.clone(), lambda);
+                Ok(Value::Symbol(name.clone()))
+            }
+            ("lambda", [parameters, body @ ..]) => Lisp::lambda(parameters.list()?, body, scope),
+            ("let", [bindings, body @ ..]) => {
+                let inner = Scope::child(scope);
+
+                for binding in bindings.list()? {
+                    let [Value::Symbol(name), value] = binding.list()? else {
+                        return Err("let bindings look like (name value)".into());
+                    };
+                    let value = eval(value)?;
+                    inner.borrow_mut().bindings.insert(name.clone(), value);
+                }
+                self.sequence(body, &inner, depth)
+            }
+            ("begin", body) => self.sequence(body, scope, depth),
+            ("and", operands) => {
+                let mut result = Value::Bool(true);
+                for operand in operands {
+                    result = eval(operand)?;
+                    if !result.is_truthy() {
+                        break;
+                    }
+                }
+                Ok(result)
+            }
+            ("or", operands) => {
+                for operand in operands {
+                    let result = eval(operand)?;
+                    if result.is_truthy() {
+                        return Ok(result);
+                    }
+                }
+                Ok(Value::Bool(false))
+            }
+            ("quote" | "if" | "define" | "lambda" | "let", _) => Err(format!("malformed {name}")),
+            _ => {
+                let function = eval(head)?;
+                let arguments = operands.iter().map(eval).collect::<Result<Vec<_>, _>>()?;
+
+                self.apply(&function, &arguments, depth)
+            }
+        }
+    }
+
+    fn sequence(
+        &self,
+        body: &[Value],
+        scope: &Rc<RefCell<Scope>>,
+        depth: usize,
+    ) -> Result<Value, String> {
+        let mut result = Value::Nil;
+        for expression in body {
+            result = self.evaluate(expression, scope, depth + 1)?;
+        }
+        Ok(result)
+    }
+
+    fn lambda(
+        parameters: &[Value],
+        body: &[Value],
+        scope: &Rc<RefCell<Scope>>,
+    ) -> Result<Value, String> {
+        let parameters = parameters
+            .iter()
+            .map(|parameter| match parameter {
+                Value::Symbol(name) => Ok(name.clone()),
+                other => Err(format!("parameters must be symbols, got {other}")),
+            })
+            .collect::<Result<_, String>>()?;
+
+        Ok(Value::Lambda(Rc::new(Lambda {
+            parameters,
+            body: body.to_vec(),
+            scope: scope.clone(),
+        })))
+    }
+
+    fn apply(&self, function: &Value, arguments: &[Value], depth: usize) -> Result<Value, String> {
+        match function {
+            Value::Builtin(_, builtin) => builtin(arguments),
+            Value::Lambda(lambda) => {
+                if lambda.parameters.len() != arguments.len() {
+                    return Err(format!(
+                        "expected {} arguments, got {}",
+                        lambda.parameters.len(),
+                        arguments.len()
+                    ));
+                }
+
+                let scope = Scope::child(&lambda.scope);
+                scope.borrow_mut().bindings.extend(
+                    lambda
+                        .parameters
+                        .iter()
+                        .cloned()
+                        .zip(arguments.iter().cloned()),
+                );
+                self.sequence(&lambda.body, &scope, depth)
+            }
+            other => Err(format!("{other} is not a function")),
+        }
+    }
+}
+
+impl Evaluator for Lisp {
+    fn name(&self) -> &'static str {
+        "lisp"
+    }
+
+    fn banner(&self) -> &'static str {
+        text::LISP_BANNER
+    }
+
+    fn is_complete(&self, source: &str) -> bool {
+        let tokens = tokenize(source);
+        let opened = tokens.iter().filter(|token| *token == "(").count();
+        let closed = tokens.iter().filter(|token| *token == ")").count();
+
+        opened <= closed
+    }
+
+    fn eval(&mut self, source: &str) -> Result<String, String> {
+        let tokens = tokenize(source);
+        let mut position = 0;
+        let mut results = Vec::new();
+        self.steps.set(0);
+
+        while position < tokens.len() {
+            let expression = parse(&tokens, &mut position)?;
+            results.push(self.evaluate(&expression, &self.global, 0)?.to_string());
+        }
+
+        Ok(results.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_evaluate_to_their_last_values() {
+        let mut lisp = Lisp::default();
+
+        assert_eq!(
+            lisp.eval("(define (square x) (* x x)) (square 12)"),
+            Ok("square\n144".to_string())
+        );
+        assert_eq!(
+            lisp.eval("(let ((x 2)) (if (> x 1) 'big 'small))"),
+            Ok("big".to_string())
+        );
+        assert_eq!(
+            lisp.eval("nothing"),
+            Err("unbound symbol: nothing".to_string())
+        );
+    }
+
+    #[test]
+    fn entries_are_complete_once_every_list_is_closed() {
+        let lisp = Lisp::default();
+
+        assert!(lisp.is_complete("(+ 1 2)"));
+        assert!(lisp.is_complete("42"));
+        assert!(!lisp.is_complete("(define (f x)\n  (* x"));
+    }
+
+    #[test]
+    fn deep_recursion_gives_up() {
+        let mut lisp = Lisp::default();
+        lisp.eval("(define (down n) (+ 1 (down n)))").unwrap();
+
+        assert_eq!(lisp.eval("(down 1)"), Err("recursion too deep".to_string()));
+    }
+
+    #[test]
+    fn long_computations_give_up_and_the_next_entry_starts_afresh() {
+        let mut lisp = Lisp::default();
+        lisp.eval("(define (fib n) (if (< n 2) n (+ (fib (- n 1)) (fib (- n 2)))))")
+            .unwrap();
+
+        assert_eq!(
+            lisp.eval("(fib 40)"),
+            Err(format!(
+                "gave up after {MAX_STEPS} steps, is something looping forever?"
+            ))
+        );
+        assert_eq!(lisp.eval("(fib 10)"), Ok("55".to_string()));
+    }
+}
@@ -0,0 +1,1201 @@
+//! A pocket Tarlox, the Lox of the portfolio, as an [`Evaluator`].
+//!
+//! A tree-walking interpreter for Lox without classes, keeping the three
+//! things Tarlox adds to the book:
+//!
+//! - Calls to functions without side effects are memoized by their
+//!   arguments. A call turns impure when it prints, assigns or reads a
+//!   variable from outside, asks for the `clock()`, or calls something
+//!   impure.
+//! - Globals initialized with a call are not waited for. Tarlox computes
+//!   them on another thread; the page has only one, so here they are
+//!   computed on first read, or once the rest of the entry has run.
+//! - `return f(...)` reuses the frame of the caller, so tail recursion
+//!   runs in constant depth while any other recursion overflows.
+//!
+//! Every entry gets a budget of steps, so that `while (true) {}` errors
+//! out instead of freezing the page.
+
+use std::{cell::RefCell, collections::HashMap, fmt, rc::Rc};
+
+use web_time::{SystemTime, UNIX_EPOCH};
+
+use super::Evaluator;
+use crate::text;
+
+/// Calls deep before the stack overflows, tail calls aside.
+const MAX_DEPTH: usize = 150;
+/// Expressions and statements an entry may evaluate.
+const MAX_STEPS: usize = 5_000_000;
+/// Memoized results kept before the cache starts over.
+const MAX_MEMOS: usize = 10_000;
+
+const KEYWORDS: &[&str] = &[
+    "and", "class", "else", "false", "for", "fun", "if", "nil", "or", "print", "return", "super",
+    "this", "true", "var", "while",
+];
+const SYMBOLS: &[&str] = &[
+    "!=", "==", ">=", "<=", "(", ")", "{", "}", ",", ".", "-", "+", ";", "/", "*", "!", "=", ">",
+    "<",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Punctuation and keywords.
+    Symbol(&'static str),
+    Identifier(String),
+    String(String),
+    Number(f64),
+    Eof,
+}
+
+#[derive(Debug, Clone)]
+struct Lexeme {
+    token: Token,
+    text: String,
+    line: usize,
+}
+
+fn scan(source: &str) -> Result<Vec<Lexeme>, String> {
+    let mut lexemes = Vec::new();
+    let mut line = 1;
+    let mut rest = source;
+
+    while let Some(next) = rest.chars().next() {
+        let length = match next {
+            '\n' => {
+                line += 1;
+                1
+            }
+            _ if next.is_whitespace() => next.len_utf8(),
+            '/' if rest.starts_with("//") => rest.find('\n').unwrap_or(rest.len()),
+            '"' => {
+                let Some(end) = rest[1..].find('"') else {
+                    return Err(format!("[line {line}] Error: Unterminated string."));
+                };
+                let string = &rest[1..end + 1];
+
+                lexemes.push(Lexeme {
+                    token: Token::String(string.to_string()),
+                    text: rest[..end + 2].to_string(),
+                    line,
+                });
+                line += string.matches('\n').count();
+                end + 2
+            }
+            _ if next.is_ascii_digit() => {
+                let mut length = rest
+                    .find(|letter: char| !letter.is_ascii_digit())
+                    .unwrap_or(rest.len());
+
+                let fraction = &rest[length..];
+                if fraction.starts_with('.')
+                    && fraction[1..].starts_with(|c: char| c.is_ascii_digit())
+                {
+                    length += 1 + fraction[1..]
+                        .find(|letter: char| !letter.is_ascii_digit())
+                        .unwrap_or(fraction.len() - 1);
+                }
+
+                let text = &rest[..length];
+                lexemes.push(Lexeme {
+                    token: Token::Number(text.parse().unwrap_or_default()),
+                    text: text.to_string(),
+                    line,
+                });
+                length
+            }
+            _ if next.is_alphabetic() || next == '_' => {
+                let length = rest
+                    .find(|letter: char| !letter.is_alphanumeric() && letter != '_')
+                    .unwrap_or(rest.len());
+                let text = &rest[..length];
+
+                let token = match KEYWORDS.iter().find(|keyword| **keyword == text) {
+                    Some(keyword) => Token::Symbol(keyword),
+                    None => Token::Identifier(text.to_string()),
+                };
+                lexemes.push(Lexeme {
+                    token,
+                    text: text.to_string(),
+                    line,
+                });
+                length
+            }
+            _ => {
+                let Some(symbol) = SYMBOLS.iter().find(|symbol| rest.starts_with(**symbol)) else {
+                    return Err(format!(
+                        "[line {line}] Error: Unexpected character '{next}'."
+                    ));
+                };
+
+                lexemes.push(Lexeme {
+                    token: Token::Symbol(symbol),
+                    text: symbol.to_string(),
+                    line,
+                });
+                symbol.len()
+            }
+        };
+
+        rest = &rest[length..];
+    }
+
+    lexemes.push(Lexeme {
+        token: Token::Eof,
+        text: String::new(),
+        line,
+    });
+    Ok(lexemes)
+}
+
+#[derive(Debug)]
+enum Expr {
+    Literal(Value),
+    Variable(String, usize),
+    Assign(String, Box<Expr>, usize),
+    Unary(&'static str, Box<Expr>, usize),
+    Binary(Box<Expr>, &'static str, Box<Expr>, usize),
+    Logical(Box<Expr>, &'static str, Box<Expr>),
+    Call(Box<Expr>, Vec<Expr>, usize),
+}
+
+impl Expr {
+    fn has_call(&self) -> bool {
+        match self {
+            Expr::Call(..) => true,
+            Expr::Literal(_) | Expr::Variable(..) => false,
+            Expr::Assign(_, value, _) | Expr::Unary(_, value, _) => value.has_call(),
+            Expr::Binary(left, _, right, _) | Expr::Logical(left, _, right) => {
+                left.has_call() || right.has_call()
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Stmt {
+    Expression(Expr),
+    Print(Expr),
+    Var(String, Option<Rc<Expr>>),
+    Block(Vec<Stmt>),
+    If(Expr, Box<Stmt>, Option<Box<Stmt>>),
+    While(Expr, Box<Stmt>),
+    Function(Rc<Function>),
+    Return(Option<Expr>),
+}
+
+#[derive(Debug)]
+struct Function {
+    name: String,
+    parameters: Vec<String>,
+    body: Vec<Stmt>,
+}
+
+struct Parser {
+    lexemes: Vec<Lexeme>,
+    current: usize,
+    /// How many function bodies the parser is inside of.
+    functions: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Lexeme {
+        &self.lexemes[self.current.min(self.lexemes.len() - 1)]
+    }
+
+    fn advance(&mut self) -> Lexeme {
+        let lexeme = self.peek().clone();
+        if lexeme.token != Token::Eof {
+            self.current += 1;
+        }
+        lexeme
+    }
+
+    fn check(&self, symbol: &str) -> bool {
+        matches!(self.peek().token, Token::Symbol(found) if found == symbol)
+    }
+
+    /// Consumes the next lexeme if it is one of `symbols`.
+    fn matches(&mut self, symbols: &[&'static str]) -> Option<&'static str> {
+        let symbol = *symbols.iter().find(|symbol| self.check(symbol))?;
+        self.advance();
+        Some(symbol)
+    }
+
+    fn error(&self, message: &str) -> String {
+        let lexeme = self.peek();
+
+        match lexeme.token {
+            Token::Eof => format!("[line {}] Error at end: {message}", lexeme.line),
+            _ => format!(
+                "[line {}] Error at '{}': {message}",
+                lexeme.line, lexeme.text
+            ),
+        }
+    }
+
+    fn expect(&mut self, symbol: &str, message: &str) -> Result<Lexeme, String> {
+        if self.check(symbol) {
+            Ok(self.advance())
+        } else {
+            Err(self.error(message))
+        }
+    }
+
+    fn identifier(&mut self, message: &str) -> Result<String, String> {
+        match &self.peek().token {
+            Token::Identifier(name) => {
+                let name = name.clone();
+                self.advance();
+                Ok(name)
+            }
+            _ => Err(self.error(message)),
+        }
+    }
+
+    fn program(&mut self) -> Result<Vec<Stmt>, String> {
+        let mut statements = Vec::new();
+
+        while self.peek().token != Token::Eof {
+            statements.push(self.declaration()?);
+        }
+        Ok(statements)
+    }
+
+    fn declaration(&mut self) -> Result<Stmt, String> {
+        if self.matches(&["fun"]).is_some() {
+            return Ok(Stmt::Function(self.function()?));
+        }
+
+        if self.matches(&["var"]).is_some() {
+            let name = self.identifier("Expect variable name.")?;
+            let initializer = match self.matches(&["="]) {
+                Some(_) => Some(Rc::new(self.expression()?)),
+                None => None,
+            };
+
+            self.expect(";", "Expect ';' after variable declaration.")?;
+            return Ok(Stmt::Var(name, initializer));
+        }
+
+        if self.check("class") {
+            return Err(self.error("Classes did not make it into the playground."));
+        }
+
+        self.statement()
+    }
+
+    fn function(&mut self) -> Result<Rc<Function>, String> {
+        let name = self.identifier("Expect function name.")?;
+        self.expect("(", "Expect '(' after function name.")?;
+
+        let mut parameters = Vec::new();
+        if !self.check(")") {
+            loop {
+                parameters.push(self.identifier("Expect parameter name.")?);
+
+                if self.matches(&[","]).is_none() {
+                    break;
+                }
+            }
+        }
+
+        self.expect(")", "Expect ')' after parameters.")?;
+        self.expect("{", "Expect '{' before function body.")?;
+
+        self.functions += 1;
+        let body = self.block();
+        self.functions -= 1;
+
+        Ok(Rc::new(Function {
+            name,
+            parameters,
+            body: body?,
+        }))
+    }
+
+    fn block(&mut self) -> Result<Vec<Stmt>, String> {
+        let mut statements = Vec::new();
+
+        while !self.check("}") && self.peek().token != Token::Eof {
+            statements.push(self.declaration()?);
+        }
+
+        self.expect("}", "Expect '}' after block.")?;
+        Ok(statements)
+    }
+
+    fn statement(&mut self) -> Result<Stmt, String> {
+        if self.matches(&["print"]).is_some() {
+            let value = self.expression()?;
+            self.expect(";", "Expect ';' after value.")?;
+            return Ok(Stmt::Print(value));
+        }
+
+        if self.check("return") {
+            if self.functions == 0 {
+                return Err(self.error("Can't return from top-level code."));
+            }
+            self.advance();
+
+            let value = match self.check(";") {
+                true => None,
+                false => Some(self.expression()?),
+            };
+            self.expect(";", "Expect ';' after return value.")?;
+            return Ok(Stmt::Return(value));
+        }
+
+        if self.matches(&["if"]).is_some() {
+            self.expect("(", "Expect '(' after 'if'.")?;
+            let condition = self.expression()?;
+            self.expect(")", "Expect ')' after if condition.")?;
+
+            let then = Box::new(self.statement()?);
+            let otherwise = match self.matches(&["else"]) {
+                Some(_) => Some(Box::new(self.statement()?)),
+                None => None,
+            };
+            return Ok(Stmt::If(condition, then, otherwise));
+        }
+
+        if self.matches(&["while"]).is_some() {
+            self.expect("(", "Expect '(' after 'while'.")?;
+            let condition = self.expression()?;
+            self.expect(")", "Expect ')' after condition.")?;
+
+            return Ok(Stmt::While(condition, Box::new(self.statement()?)));
+        }
+
+        if self.matches(&["for"]).is_some() {
+            return self.for_statement();
+        }
+
+        if self.matches(&["{"]).is_some() {
+            return Ok(Stmt::Block(self.block()?));
+        }
+
+        let expression = self.expression()?;
+        self.expect(";", "Expect ';' after expression.")?;
+        Ok(Stmt::Expression(expression))
+    }
+
+    /// Desugars `for` into a `while` inside a block, as the book does.
+    fn for_statement(&mut self) -> Result<Stmt, String> {
+        self.expect("(", "Expect '(' after 'for'.")?;
+
+        let initializer = if self.matches(&[";"]).is_some() {
+            None
+        } else if self.check("var") {
+            Some(self.declaration()?)
+        } else {
+            let expression = self.expression()?;
+            self.expect(";", "Expect ';' after loop initializer.")?;
+            Some(Stmt::Expression(expression))
+        };
+
+        let condition = match self.check(";") {
+            true => Expr::Literal(Value::Bool(true)),
+            false => self.expression()?,
+        };
+        self.expect(";", "Expect ';' after loop condition.")?;
+
+        let increment = match self.check(")") {
+            true => None,
+            false => Some(self.expression()?),
+        };
+        self.expect(")", "Expect ')' after for clauses.")?;
+
+        let mut body = self.statement()?;
+        if let Some(increment) = increment {
+            body = Stmt::Block(vec![body, Stmt::Expression(increment)]);
+        }
+
+        let mut statements: Vec<Stmt> = initializer.into_iter().collect();
+        statements.push(Stmt::While(condition, Box::new(body)));
+        Ok(Stmt::Block(statements))
+    }
+
+    fn expression(&mut self) -> Result<Expr, String> {
+        let target = self.or()?;
+
+        if self.check("=") {
+            let line = self.advance().line;
+            let value = self.expression()?;
+
+            return match target {
+                Expr::Variable(name, _) => Ok(Expr::Assign(name, Box::new(value), line)),
+                _ => Err(format!(
+                    "[line {line}] Error at '=': Invalid assignment target."
+                )),
+            };
+        }
+        Ok(target)
+    }
+
+    fn or(&mut self) -> Result<Expr, String> {
+        let mut expression = self.and()?;
+
+        while let Some(operator) = self.matches(&["or"]) {
+            expression = Expr::Logical(Box::new(expression), operator, Box::new(self.and()?));
+        }
+        Ok(expression)
+    }
+
+    fn and(&mut self) -> Result<Expr, String> {
+        let mut expression = self.binary(0)?;
+
+        while let Some(operator) = self.matches(&["and"]) {
+            expression = Expr::Logical(Box::new(expression), operator, Box::new(self.binary(0)?));
+        }
+        Ok(expression)
+    }
+
+    /// Equality, comparison, terms and factors, loosest first.
+    fn binary(&mut self, level: usize) -> Result<Expr, String> {
+        const LEVELS: [&[&str]; 4] = [
+            &["!=", "=="],
+            &[">", ">=", "<", "<="],
+            &["-", "+"],
+            &["/", "*"],
+        ];
+
+        let Some(operators) = LEVELS.get(level) else {
+            return self.unary();
+        };
+
+        let mut expression = self.binary(level + 1)?;
+        loop {
+            let line = self.peek().line;
+            let Some(operator) = self.matches(operators) else {
+                break;
+            };
+
+            let right = self.binary(level + 1)?;
+            expression = Expr::Binary(Box::new(expression), operator, Box::new(right), line);
+        }
+        Ok(expression)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        let line = self.peek().line;
+
+        match self.matches(&["!", "-"]) {
+            Some(operator) => Ok(Expr::Unary(operator, Box::new(self.unary()?), line)),
+            None => self.call(),
+        }
+    }
+
+    fn call(&mut self) -> Result<Expr, String> {
+        let mut expression = self.primary()?;
+
+        while self.matches(&["("]).is_some() {
+            let mut arguments = Vec::new();
+
+            if !self.check(")") {
+                loop {
+                    arguments.push(self.expression()?);
+
+                    if self.matches(&[","]).is_none() {
+                        break;
+                    }
+                }
+            }
+
+            let line = self.expect(")", "Expect ')' after arguments.")?.line;
+            expression = Expr::Call(Box::new(expression), arguments, line);
+        }
+        Ok(expression)
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        let lexeme = self.peek().clone();
+
+        let expression = match lexeme.token {
+            Token::Number(number) => Expr::Literal(Value::Number(number)),
+            Token::String(string) => Expr::Literal(Value::String(string.into())),
+            Token::Identifier(name) => Expr::Variable(name, lexeme.line),
+            Token::Symbol("true") => Expr::Literal(Value::Bool(true)),
+            Token::Symbol("false") => Expr::Literal(Value::Bool(false)),
+            Token::Symbol("nil") => Expr::Literal(Value::Nil),
+            Token::Symbol("(") => {
+                self.advance();
+                let expression = self.expression()?;
+                self.expect(")", "Expect ')' after expression.")?;
+                return Ok(expression);
+            }
+            _ => return Err(self.error("Expect expression.")),
+        };
+
+        self.advance();
+        Ok(expression)
+    }
+}
+
+type Native = fn() -> Value;
+
+#[derive(Clone)]
+enum Value {
+    Nil,
+    Bool(bool),
+    Number(f64),
+    String(Rc<str>),
+    Function(Rc<Closure>),
+    Native(&'static str, Native),
+}
+
+impl fmt::Debug for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{self}")
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Nil => write!(f, "nil"),
+            Value::Bool(value) => write!(f, "{value}"),
+            Value::Number(number) => write!(f, "{number}"),
+            Value::String(string) => write!(f, "{string}"),
+            Value::Function(closure) => write!(f, "<fn {}>", closure.function.name),
+            Value::Native(..) => write!(f, "<native fn>"),
+        }
+    }
+}
+
+impl Value {
+    fn is_truthy(&self) -> bool {
+        !matches!(self, Value::Nil | Value::Bool(false))
+    }
+
+    fn equals(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Nil, Value::Nil) => true,
+            (Value::Bool(left), Value::Bool(right)) => left == right,
+            (Value::Number(left), Value::Number(right)) => left == right,
+            (Value::String(left), Value::String(right)) => left == right,
+            (Value::Function(left), Value::Function(right)) => Rc::ptr_eq(left, right),
+            (Value::Native(left, _), Value::Native(right, _)) => left == right,
+            _ => false,
+        }
+    }
+
+    /// How the value goes into a memo key, if it can.
+    fn key(&self) -> Option<String> {
+        match self {
+            Value::Nil => Some("nil".into()),
+            Value::Bool(value) => Some(value.to_string()),
+            Value::Number(number) => Some(format!("{:x}", number.to_bits())),
+            Value::String(string) => Some(format!("{string:?}")),
+            Value::Function(_) | Value::Native(..) => None,
+        }
+    }
+}
+
+struct Closure {
+    function: Rc<Function>,
+    scope: Rc<RefCell<Scope>>,
+}
+
+enum Slot {
+    Ready(Value),
+    /// A global whose initializer has not run yet.
+    Deferred(Rc<Expr>),
+}
+
+struct Scope {
+    slots: HashMap<String, Slot>,
+    parent: Option<Rc<RefCell<Scope>>>,
+    /// The call that made the scope, zero for the top level.
+    frame: usize,
+}
+
+impl Scope {
+    fn new(parent: Option<Rc<RefCell<Scope>>>, frame: usize) -> Rc<RefCell<Scope>> {
+        Rc::new(RefCell::new(Scope {
+            slots: HashMap::new(),
+            parent,
+            frame,
+        }))
+    }
+
+    fn define(&mut self, name: &str, value: Value) {
+        self.slots.insert(name.to_string(), Slot::Ready(value));
+    }
+}
+
+/// Finds the scope a variable lives in.
+fn lookup(scope: &Rc<RefCell<Scope>>, name: &str) -> Option<Rc<RefCell<Scope>>> {
+    let mut scope = scope.clone();
+
+    loop {
+        if scope.borrow().slots.contains_key(name) {
+            return Some(scope);
+        }
+
+        let parent = scope.borrow().parent.clone()?;
+        scope = parent;
+    }
+}
+
+enum Flow {
+    Normal,
+    Return(Value),
+    /// `return f(...)`, to be run in place of the current call.
+    TailCall(Rc<Closure>, Vec<Value>),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    id: usize,
+    pure: bool,
+}
+
+pub struct Lox {
+    globals: Rc<RefCell<Scope>>,
+    memos: HashMap<(usize, String), (Rc<Closure>, Value)>,
+    frames: Vec<Frame>,
+    next_frame: usize,
+    steps: usize,
+    output: String,
+    deferred: Vec<String>,
+}
+
+impl fmt::Debug for Lox {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Lox")
+            .field("memos", &self.memos.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for Lox {
+    fn default() -> Self {
+        let globals = Scope::new(None, 0);
+        globals.borrow_mut().define(
+            "clock",
+            Value::Native("clock", || {
+                let now = SystemTime::now().duration_since(UNIX_EPOCH);
+                Value::Number(now.unwrap_or_default().as_secs_f64())
+            }),
+        );
+
+        Self {
+            globals,
+            memos: HashMap::new(),
+            frames: Vec::new(),
+            next_frame: 0,
+            steps: 0,
+            output: String::new(),
+            deferred: Vec::new(),
+        }
+    }
+}
+
+fn runtime(message: &str, line: usize) -> String {
+    format!("{message}\n[line {line}]")
+}
+
+impl Lox {
+    fn current_frame(&self) -> usize {
+        self.frames.last().map_or(0, |frame| frame.id)
+    }
+
+    /// Marks the running call as having side effects.
+    fn impure(&mut self) {
+        if let Some(frame) = self.frames.last_mut() {
+            frame.pure = false;
+        }
+    }
+
+    fn step(&mut self) -> Result<(), String> {
+        self.steps += 1;
+
+        if self.steps > MAX_STEPS {
+            return Err(format!(
+                "Gave up after {MAX_STEPS} steps. Is something looping forever?"
+            ));
+        }
+        Ok(())
+    }
+
+    fn get(
+        &mut self,
+        scope: &Rc<RefCell<Scope>>,
+        name: &str,
+        line: usize,
+    ) -> Result<Value, String> {
+        let Some(owner) = lookup(scope, name) else {
+            return Err(runtime(&format!("Undefined variable '{name}'."), line));
+        };
+
+        let deferred = match owner.borrow().slots.get(name) {
+            Some(Slot::Deferred(initializer)) => Some(initializer.clone()),
+            _ => None,
+        };
+        if let Some(initializer) = deferred {
+            self.force(name, &initializer)?;
+        }
+
+        let value = match owner.borrow().slots.get(name) {
+            Some(Slot::Ready(value)) => value.clone(),
+            _ => Value::Nil,
+        };
+
+        // Functions are looked up by name, which does not make a call depend
+        // on outside state the way reading a variable does.
+        let outside = owner.borrow().frame != self.current_frame();
+        if outside && !matches!(value, Value::Function(_) | Value::Native(..)) {
+            self.impure();
+        }
+
+        Ok(value)
+    }
+
+    /// Runs the initializer of a deferred global.
+    fn force(&mut self, name: &str, initializer: &Expr) -> Result<(), String> {
+        let globals = self.globals.clone();
+
+        // The variable reads as nil while it is being computed.
+        globals.borrow_mut().define(name, Value::Nil);
+        let value = self.evaluate(initializer, &globals)?;
+        globals.borrow_mut().define(name, value);
+        Ok(())
+    }
+
+    fn assign(
+        &mut self,
+        scope: &Rc<RefCell<Scope>>,
+        name: &str,
+        value: Value,
+        line: usize,
+    ) -> Result<(), String> {
+        let Some(owner) = lookup(scope, name) else {
+            return Err(runtime(&format!("Undefined variable '{name}'."), line));
+        };
+
+        if owner.borrow().frame != self.current_frame() {
+            self.impure();
+        }
+
+        owner.borrow_mut().define(name, value);
+        Ok(())
+    }
+
+    fn execute_block(
+        &mut self,
+        statements: &[Stmt],
+        scope: &Rc<RefCell<Scope>>,
+    ) -> Result<Flow, String> {
+        for statement in statements {
+            match self.execute(statement, scope)? {
+                Flow::Normal => {}
+                flow => return Ok(flow),
+            }
+        }
+        Ok(Flow::Normal)
+    }
+
+    fn execute(&mut self, statement: &Stmt, scope: &Rc<RefCell<Scope>>) -> Result<Flow, String> {
+        self.step()?;
+
+        match statement {
+            Stmt::Expression(expression) => {
+                self.evaluate(expression, scope)?;
+            }
+            Stmt::Print(expression) => {
+                let value = self.evaluate(expression, scope)?;
+                self.output.push_str(&format!("{value}\n"));
+                self.impure();
+            }
+            Stmt::Var(name, Some(initializer))
+                if initializer.has_call() && Rc::ptr_eq(scope, &self.globals) =>
+            {
+                self.globals
+                    .borrow_mut()
+                    .slots
+                    .insert(name.clone(), Slot::Deferred(initializer.clone()));
+                self.deferred.push(name.clone());
+            }
+            Stmt::Var(name, initializer) => {
+                let value = match initializer {
+                    Some(initializer) => self.evaluate(initializer, scope)?,
+                    None => Value::Nil,
+                };
+                scope.borrow_mut().define(name, value);
+            }
+            Stmt::Block(statements) => {
+                let inner = Scope::new(Some(scope.clone()), self.current_frame());
+                return self.execute_block(statements, &inner);
+            }
+            Stmt::If(condition, then, otherwise) => {
+                if self.evaluate(condition, scope)?.is_truthy() {
+                    return self.execute(then, scope);
+                } else if let Some(otherwise) = otherwise {
+                    return self.execute(otherwise, scope);
+                }
+            }
+            Stmt::While(condition, body) => {
+                while self.evaluate(condition, scope)?.is_truthy() {
+                    match self.execute(body, scope)? {
+                        Flow::Normal => {}
+                        flow => return Ok(flow),
+                    }
+                }
+            }
+            Stmt::Function(function) => {
+                let closure = Closure {
+                    function: function.clone(),
+                    scope: scope.clone(),
+                };
+                scope
+                    .borrow_mut()
+                    .define(&function.name, Value::Function(Rc::new(closure)));
+            }
+            Stmt::Return(Some(Expr::Call(callee, arguments, line))) => {
+                let callee = self.evaluate(callee, scope)?;
+                let arguments = self.arguments(arguments, scope)?;
+
+                return match callee {
+                    Value::Function(closure) => Ok(Flow::TailCall(closure, arguments)),
+                    callee => Ok(Flow::Return(self.call(callee, arguments, *line)?)),
+                };
+            }
+            Stmt::Return(value) => {
+                let value = match value {
+                    Some(value) => self.evaluate(value, scope)?,
+                    None => Value::Nil,
+                };
+                return Ok(Flow::Return(value));
+            }
+        }
+
+        Ok(Flow::Normal)
+    }
+
+    fn arguments(
+        &mut self,
+        arguments: &[Expr],
+        scope: &Rc<RefCell<Scope>>,
+    ) -> Result<Vec<Value>, String> {
+        arguments
+            .iter()
+            .map(|argument| self.evaluate(argument, scope))
+            .collect()
+    }
+
+    fn evaluate(&mut self, expression: &Expr, scope: &Rc<RefCell<Scope>>) -> Result<Value, String> {
+        match expression {
+            Expr::Literal(value) => Ok(value.clone()),
+            Expr::Variable(name, line) => self.get(scope, name, *line),
+            Expr::Assign(name, value, line) => {
+                let value = self.evaluate(value, scope)?;
+                self.assign(scope, name, value.clone(), *line)?;
+                Ok(value)
+            }
+            Expr::Unary(operator, operand, line) => {
+                match (*operator, self.evaluate(operand, scope)?) {
+                    ("!", operand) => Ok(Value::Bool(!operand.is_truthy())),
+                    (_, Value::Number(number)) => Ok(Value::Number(-number)),
+                    _ => Err(runtime("Operand must be a number.", *line)),
+                }
+            }
+            Expr::Logical(left, operator, right) => {
+                let left = self.evaluate(left, scope)?;
+
+                match (*operator, left.is_truthy()) {
+                    ("or", true) | ("and", false) => Ok(left),
+                    _ => self.evaluate(right, scope),
+                }
+            }
+            Expr::Binary(left, operator, right, line) => {
+                self.step()?;
+                let left = self.evaluate(left, scope)?;
+                let right = self.evaluate(right, scope)?;
+
+                match (*operator, left, right) {
+                    ("==", left, right) => Ok(Value::Bool(left.equals(&right))),
+                    ("!=", left, right) => Ok(Value::Bool(!left.equals(&right))),
+                    ("+", Value::String(left), Value::String(right)) => {
+                        Ok(Value::String(format!("{left}{right}").into()))
+                    }
+                    ("+", Value::Number(left), Value::Number(right)) => {
+                        Ok(Value::Number(left + right))
+                    }
+                    ("+", ..) => Err(runtime(
+                        "Operands must be two numbers or two strings.",
+                        *line,
+                    )),
+                    (operator, Value::Number(left), Value::Number(right)) => Ok(match operator {
+                        "-" => Value::Number(left - right),
+                        "*" => Value::Number(left * right),
+                        "/" => Value::Number(left / right),
+                        ">" => Value::Bool(left > right),
+                        ">=" => Value::Bool(left >= right),
+                        "<" => Value::Bool(left < right),
+                        _ => Value::Bool(left <= right),
+                    }),
+                    _ => Err(runtime("Operands must be numbers.", *line)),
+                }
+            }
+            Expr::Call(callee, arguments, line) => {
+                let callee = self.evaluate(callee, scope)?;
+                let arguments = self.arguments(arguments, scope)?;
+                self.call(callee, arguments, *line)
+            }
+        }
+    }
+
+    fn call(&mut self, callee: Value, arguments: Vec<Value>, line: usize) -> Result<Value, String> {
+        match callee {
+            Value::Native(_, native) if arguments.is_empty() => {
+                self.impure();
+                Ok(native())
+            }
+            Value::Native(..) => Err(runtime(
+                &format!("Expected 0 arguments but got {}.", arguments.len()),
+                line,
+            )),
+            Value::Function(closure) => self.call_closure(closure, arguments, line),
+            _ => Err(runtime("Can only call functions and classes.", line)),
+        }
+    }
+
+    fn call_closure(
+        &mut self,
+        closure: Rc<Closure>,
+        arguments: Vec<Value>,
+        line: usize,
+    ) -> Result<Value, String> {
+        let key = arguments
+            .iter()
+            .map(Value::key)
+            .collect::<Option<Vec<_>>>()
+            .map(|keys| (Rc::as_ptr(&closure) as usize, keys.join(",")));
+
+        if let Some((_, value)) = key.as_ref().and_then(|key| self.memos.get(key)) {
+            return Ok(value.clone());
+        }
+
+        let (mut callee, mut arguments) = (closure.clone(), arguments);
+        let mut pure = true;
+
+        let value = loop {
+            let function = callee.function.clone();
+
+            if function.parameters.len() != arguments.len() {
+                return Err(runtime(
+                    &format!(
+                        "Expected {} arguments but got {}.",
+                        function.parameters.len(),
+                        arguments.len()
+                    ),
+                    line,
+                ));
+            }
+
+            if self.frames.len() >= MAX_DEPTH {
+                return Err(runtime("Stack overflow.", line));
+            }
+
+            self.next_frame += 1;
+            let frame = Frame {
+                id: self.next_frame,
+                pure: true,
+            };
+            self.frames.push(frame);
+
+            let scope = Scope::new(Some(callee.scope.clone()), frame.id);
+            for (parameter, argument) in function.parameters.iter().zip(arguments) {
+                scope.borrow_mut().define(parameter, argument);
+            }
+
+            let flow = self.execute_block(&function.body, &scope);
+            pure &= self.frames.pop().is_some_and(|frame| frame.pure);
+
+            match flow? {
+                Flow::Normal => break Value::Nil,
+                Flow::Return(value) => break value,
+                Flow::TailCall(next, next_arguments) => {
+                    callee = next;
+                    arguments = next_arguments;
+                }
+            }
+        };
+
+        // A function made by the call closes over a scope of its own, which
+        // a remembered copy would share between calls. Without objects,
+        // returning it is the only way out for it that leaves the call pure.
+        match key {
+            _ if !pure => self.impure(),
+            _ if matches!(value, Value::Function(_)) => {}
+            Some(key) => {
+                if self.memos.len() >= MAX_MEMOS {
+                    self.memos.clear();
+                }
+                self.memos.insert(key, (closure, value.clone()));
+            }
+            None => {}
+        }
+
+        Ok(value)
+    }
+
+    fn run(&mut self, statements: &[Stmt]) -> Result<(), String> {
+        let globals = self.globals.clone();
+        self.execute_block(statements, &globals)?;
+
+        // Whatever was left computing in the background is done by now.
+        for name in std::mem::take(&mut self.deferred) {
+            let deferred = match globals.borrow().slots.get(&name) {
+                Some(Slot::Deferred(initializer)) => Some(initializer.clone()),
+                _ => None,
+            };
+
+            if let Some(initializer) = deferred {
+                self.force(&name, &initializer)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse(source: &str) -> Result<Vec<Stmt>, String> {
+    Parser {
+        lexemes: scan(source)?,
+        current: 0,
+        functions: 0,
+    }
+    .program()
+}
+
+impl Evaluator for Lox {
+    fn name(&self) -> &'static str {
+        "tarlox"
+    }
+
+    fn banner(&self) -> &'static str {
+        text::LOX_BANNER
+    }
+
+    fn is_complete(&self, source: &str) -> bool {
+        let Ok(lexemes) = scan(source) else {
+            // An unterminated string carries on to the next line.
+            return false;
+        };
+
+        let depth = lexemes.iter().fold(0, |depth, lexeme| match lexeme.token {
+            Token::Symbol("(" | "{") => depth + 1,
+            Token::Symbol(")" | "}") => depth - 1,
+            _ => depth,
+        });
+        depth <= 0
+    }
+
+    /// Runs the entry as a program, or prints it if it is a lone
+    /// expression.
+    fn eval(&mut self, source: &str) -> Result<String, String> {
+        let statements = parse(source).or_else(|error| {
+            let expression = source.trim().trim_end_matches(';');
+            parse(&format!("print {expression};")).map_err(|_| error)
+        })?;
+
+        self.steps = 0;
+        self.frames.clear();
+        self.deferred.clear();
+
+        self.run(&statements).map(|()| String::new())
+    }
+
+    fn take_output(&mut self) -> String {
+        std::mem::take(&mut self.output)
+    }
+
+    fn examples(&self) -> &'static [(&'static str, &'static str)] {
+        text::LOX_EXAMPLES
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs an entry on a fresh interpreter, returning what it printed
+    /// and how it ended.
+    fn run(source: &str) -> (String, Result<String, String>) {
+        let mut lox = Lox::default();
+        let result = lox.eval(source);
+
+        (lox.take_output(), result)
+    }
+
+    fn example(name: &str) -> &'static str {
+        text::LOX_EXAMPLES
+            .iter()
+            .find(|(example, _)| *example == name)
+            .map(|(_, source)| *source)
+            .unwrap()
+    }
+
+    #[test]
+    fn memoization_computes_fib_of_ninety() {
+        let (output, result) = run(example("memoization"));
+
+        assert_eq!(output, "2880067194370816000\n");
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn functions_made_by_a_call_are_not_remembered() {
+        let (output, result) = run(
+            "fun make() { var n = 0; fun inc() { n = n + 1; print n; } return inc; }\n\
+             var a = make(); var b = make(); a(); b(); a();",
+        );
+
+        assert_eq!(output, "1\n1\n2\n");
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn globals_initialized_with_a_call_are_computed_last() {
+        let (output, result) = run(example("non-blocking"));
+
+        assert_eq!(
+            output,
+            "answer is declared, moving on\n...slow() is done\n5000050000\n"
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn tail_calls_go_deep_where_other_calls_overflow() {
+        let (output, result) = run(example("tail-recursion"));
+
+        assert_eq!(output, "5000050000\n");
+        assert!(result.unwrap_err().contains("Stack overflow."));
+    }
+
+    #[test]
+    fn strings_have_to_be_closed() {
+        let (_, result) = run(r#"print "open;"#);
+
+        assert_eq!(result.unwrap_err(), "[line 1] Error: Unterminated string.");
+    }
+
+    #[test]
+    fn variables_have_to_be_declared() {
+        let (_, result) = run("print missing;");
+
+        assert!(result
+            .unwrap_err()
+            .contains("Undefined variable 'missing'."));
+    }
+
+    #[test]
+    fn endless_loops_run_out_of_steps() {
+        let (_, result) = run("while (true) {}");
+
+        assert!(result.unwrap_err().contains("Gave up after"));
+    }
+}
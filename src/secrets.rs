@@ -0,0 +1,385 @@
+//! Registry of key sequence easter eggs.
+//!
+//! Each [`Secret`] describes how it is triggered, how forgiving it is and
+//! what happens once it is completed. [`Secrets`] keeps the progress of all
+//! of them and is fed every key the menu and the content pane receive.
+
+use ratzilla::event::KeyCode;
+use web_time::{Duration, Instant};
+
+use crate::{keymap::Chord, storage, text};
+
+#[derive(Debug, Clone, Copy)]
+pub enum Trigger {
+    /// Keys pressed one after another.
+    Keys(&'static [KeyCode]),
+    /// A word typed one letter after another, in lowercase.
+    Word(&'static str),
+    /// Keys pressed together in any order, all within the timeout.
+    Chord(&'static [KeyCode]),
+}
+
+impl Trigger {
+    fn len(self) -> usize {
+        match self {
+            Trigger::Keys(keys) | Trigger::Chord(keys) => keys.len(),
+            Trigger::Word(word) => word.chars().count(),
+        }
+    }
+
+    /// Human readable form of the trigger, e.g. `t-a-r-b-e-t-u`.
+    pub fn label(self) -> String {
+        let separator = match self {
+            Trigger::Chord(_) => "+",
+            _ => "-",
+        };
+
+        (0..self.len())
+            .filter_map(|index| self.step(index))
+            .map(|key| Chord::Key(key).label())
+            .collect::<Vec<_>>()
+            .join(separator)
+    }
+
+    fn step(self, index: usize) -> Option<KeyCode> {
+        match self {
+            Trigger::Keys(keys) | Trigger::Chord(keys) => keys.get(index).copied(),
+            Trigger::Word(word) => word
+                .chars()
+                .nth(index)
+                .map(|letter| lowercase(KeyCode::Char(letter))),
+        }
+    }
+}
+
+/// Lowercases a letter the Unicode way, so a title word with ı or ş
+/// can be typed too.
+fn lowercase(key: KeyCode) -> KeyCode {
+    match key {
+        KeyCode::Char(letter) => KeyCode::Char(letter.to_lowercase().next().unwrap_or(letter)),
+        key => key,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    Popup {
+        title: &'static str,
+        message: &'static str,
+    },
+    UnlockPage,
+    Party,
+}
+
+#[derive(Debug)]
+pub struct Secret {
+    pub name: &'static str,
+    pub trigger: Trigger,
+    /// Longest pause between two keys before the progress is thrown away.
+    pub timeout: Option<Duration>,
+    /// Whether a wrong key throws the progress away.
+    pub reset_on_mistake: bool,
+    /// Whether matching keys are swallowed instead of reaching the keymap.
+    pub consumes: bool,
+    /// Whether the secret starts over after it is completed.
+    pub repeatable: bool,
+    /// Whether the help overlay spells the trigger out before the
+    /// secret is found.
+    pub public: bool,
+    pub effect: Effect,
+}
+
+pub const TARBETU: &str = "tarbetu";
+
+pub const SECRETS: &[Secret] = &[
+    Secret {
+        name: TARBETU,
+        trigger: Trigger::Word(text::TITLE_WORD),
+        timeout: None,
+        reset_on_mistake: false,
+        consumes: true,
+        repeatable: false,
+        public: true,
+        effect: Effect::Popup {
+            title: " 0x54617262657475 ",
+            message: text::SECRET_FOUND,
+        },
+    },
+    Secret {
+        name: "konami",
+        trigger: Trigger::Keys(&[
+            KeyCode::Up,
+            KeyCode::Up,
+            KeyCode::Down,
+            KeyCode::Down,
+            KeyCode::Left,
+            KeyCode::Right,
+            KeyCode::Left,
+            KeyCode::Right,
+            KeyCode::Char('b'),
+            KeyCode::Char('a'),
+        ]),
+        timeout: Some(Duration::from_secs(2)),
+        reset_on_mistake: true,
+        consumes: false,
+        repeatable: true,
+        public: false,
+        effect: Effect::Party,
+    },
+    Secret {
+        name: "sudo",
+        trigger: Trigger::Word("sudo"),
+        timeout: Some(Duration::from_millis(1500)),
+        reset_on_mistake: true,
+        consumes: false,
+        repeatable: false,
+        public: false,
+        effect: Effect::UnlockPage,
+    },
+    Secret {
+        name: "hjkl",
+        trigger: Trigger::Chord(&[
+            KeyCode::Char('h'),
+            KeyCode::Char('j'),
+            KeyCode::Char('k'),
+            KeyCode::Char('l'),
+        ]),
+        timeout: Some(Duration::from_millis(300)),
+        reset_on_mistake: true,
+        consumes: false,
+        repeatable: true,
+        public: false,
+        effect: Effect::Popup {
+            title: " :wq ",
+            message: text::HJKL_FOUND,
+        },
+    },
+];
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Progress {
+    /// Matched steps of a sequence, or a bitmask of the pressed chord keys.
+    matched: usize,
+    last: Option<Instant>,
+    found: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct Outcome {
+    pub consumed: bool,
+    pub effects: Vec<Effect>,
+    /// Names of the secrets completed by this key.
+    pub found: Vec<&'static str>,
+}
+
+#[derive(Debug)]
+pub struct Secrets {
+    progress: [Progress; SECRETS.len()],
+}
+
+impl Default for Secrets {
+    fn default() -> Self {
+        let mut progress = [Progress::default(); SECRETS.len()];
+
+        if let Some(found) = storage::get("secrets") {
+            for (secret, progress) in SECRETS.iter().zip(progress.iter_mut()) {
+                progress.found = found.split(',').any(|name| name == secret.name);
+
+                // Found secrets that don't start over stay completed, which
+                // keeps the title decoded after a reload.
+                if progress.found && !secret.repeatable {
+                    progress.matched = secret.trigger.len();
+                }
+            }
+        }
+
+        Self { progress }
+    }
+}
+
+impl Secrets {
+    pub fn feed(&mut self, key: KeyCode, now: Instant) -> Outcome {
+        let mut outcome = Outcome::default();
+
+        for (secret, progress) in SECRETS.iter().zip(self.progress.iter_mut()) {
+            if !secret.repeatable && progress.matched == secret.trigger.len() {
+                continue;
+            }
+
+            let timed_out = matches!(
+                (secret.timeout, progress.last),
+                (Some(timeout), Some(last)) if now.duration_since(last) > timeout
+            );
+            if timed_out {
+                progress.matched = 0;
+                progress.last = None;
+            }
+
+            let advanced = match secret.trigger {
+                Trigger::Keys(_) | Trigger::Word(_) => Secrets::advance(secret, progress, key),
+                Trigger::Chord(keys) => Secrets::press(secret, progress, keys, key),
+            };
+
+            if !advanced {
+                continue;
+            }
+
+            // The timeout of a chord counts from its first key.
+            if progress.last.is_none() || !matches!(secret.trigger, Trigger::Chord(_)) {
+                progress.last = Some(now);
+            }
+            outcome.consumed |= secret.consumes;
+
+            let completed = match secret.trigger {
+                Trigger::Chord(keys) => progress.matched == (1 << keys.len()) - 1,
+                _ => progress.matched == secret.trigger.len(),
+            };
+
+            if completed {
+                progress.found = true;
+                outcome.effects.push(secret.effect);
+                outcome.found.push(secret.name);
+
+                if secret.repeatable {
+                    progress.matched = 0;
+                    progress.last = None;
+                }
+            }
+        }
+
+        if !outcome.effects.is_empty() {
+            self.save();
+        }
+
+        outcome
+    }
+
+    fn advance(secret: &Secret, progress: &mut Progress, key: KeyCode) -> bool {
+        let key = match secret.trigger {
+            Trigger::Word(_) => lowercase(key),
+            Trigger::Keys(_) | Trigger::Chord(_) => key,
+        };
+
+        if secret.trigger.step(progress.matched) == Some(key) {
+            progress.matched += 1;
+            return true;
+        }
+
+        if secret.reset_on_mistake {
+            progress.matched = Secrets::fallback(secret.trigger, progress.matched, key);
+            if progress.matched == 0 {
+                progress.last = None;
+            }
+            return progress.matched > 0;
+        }
+
+        false
+    }
+
+    /// How much of the trigger is still matched after `key` broke a run of
+    /// `matched` steps: the longest prefix of the trigger that ends the keys
+    /// typed so far, as the failure function of KMP would have it. Up, Up,
+    /// Up keeps two steps of the Konami code instead of one.
+    fn fallback(trigger: Trigger, matched: usize, key: KeyCode) -> usize {
+        (1..=matched)
+            .rev()
+            .find(|&length| {
+                trigger.step(length - 1) == Some(key)
+                    && (0..length - 1).all(|index| {
+                        trigger.step(index) == trigger.step(matched - length + 1 + index)
+                    })
+            })
+            .unwrap_or(0)
+    }
+
+    fn press(secret: &Secret, progress: &mut Progress, keys: &[KeyCode], key: KeyCode) -> bool {
+        match keys.iter().position(|chord_key| *chord_key == key) {
+            Some(index) => {
+                progress.matched |= 1 << index;
+                true
+            }
+            None => {
+                if secret.reset_on_mistake {
+                    progress.matched = 0;
+                    progress.last = None;
+                }
+                false
+            }
+        }
+    }
+
+    /// How many steps of the named secret are matched so far.
+    pub fn progress(&self, name: &str) -> usize {
+        SECRETS
+            .iter()
+            .position(|secret| secret.name == name)
+            .map_or(0, |index| self.progress[index].matched)
+    }
+
+    pub fn is_found(&self, name: &str) -> bool {
+        SECRETS
+            .iter()
+            .position(|secret| secret.name == name)
+            .is_some_and(|index| self.progress[index].found)
+    }
+
+    pub fn hints(&self) -> impl Iterator<Item = (&'static Secret, bool)> + '_ {
+        SECRETS
+            .iter()
+            .zip(self.progress.iter())
+            .map(|(secret, progress)| (secret, progress.found))
+    }
+
+    fn save(&self) {
+        let found = self
+            .hints()
+            .filter(|(_, found)| *found)
+            .map(|(secret, _)| secret.name)
+            .collect::<Vec<_>>()
+            .join(",");
+
+        storage::set("secrets", &found);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn konami() -> Trigger {
+        SECRETS
+            .iter()
+            .find(|secret| secret.name == "konami")
+            .map(|secret| secret.trigger)
+            .unwrap()
+    }
+
+    #[test]
+    fn a_third_up_keeps_two_steps_of_the_konami_code() {
+        assert_eq!(Secrets::fallback(konami(), 2, KeyCode::Up), 2);
+    }
+
+    #[test]
+    fn a_wrong_key_starts_over() {
+        assert_eq!(Secrets::fallback(konami(), 3, KeyCode::Char('x')), 0);
+        assert_eq!(Secrets::fallback(konami(), 4, KeyCode::Up), 1);
+    }
+
+    #[test]
+    fn words_match_whatever_the_case() {
+        let secret = Secret {
+            trigger: Trigger::Word("Işık"),
+            ..SECRETS[0]
+        };
+        let mut progress = Progress::default();
+
+        for letter in ['I', 'Ş', 'ı', 'K'] {
+            assert!(Secrets::advance(
+                &secret,
+                &mut progress,
+                KeyCode::Char(letter)
+            ));
+        }
+        assert_eq!(progress.matched, 4);
+    }
+}
@@ -0,0 +1,297 @@
+//! The few shell commands reachable from the gdb prompt through `shell`.
+//!
+//! They all read the [`vfs`](crate::vfs) tree, from the home directory,
+//! and answer with what coreutils would print, errors included.
+
+use crate::vfs::{self, Kind, Node};
+
+/// Runs a command line and returns its output. Without `sudo` the file
+/// behind the hidden page stays unreadable, as the menu keeps it hidden.
+pub fn run(line: &str, sudo: bool) -> String {
+    let mut words = line.split_whitespace();
+    let Some(command) = words.next() else {
+        return String::new();
+    };
+
+    let (flags, paths): (Vec<&str>, Vec<&str>) = words.partition(|word| word.starts_with('-'));
+    let flags: String = flags.iter().flat_map(|flag| flag.chars().skip(1)).collect();
+
+    match command {
+        "ls" => ls(&paths, flags.contains('l'), flags.contains('a')),
+        "tree" => tree(paths.first().copied().unwrap_or("."), flags.contains('a')),
+        "cat" => each(command, &paths, sudo, cat),
+        "file" => each(command, &paths, sudo, file),
+        "pwd" => vfs::HOME.to_string(),
+        "whoami" => vfs::USER.to_string(),
+        "cd" => format!("bash: cd: not while gdb holds on to {}/core", vfs::HOME),
+        other => format!("bash: {other}: command not found"),
+    }
+}
+
+/// Runs `command` on every path, as `cat` and `file` do, or complains
+/// about the missing operand.
+fn each(
+    command: &str,
+    paths: &[&str],
+    sudo: bool,
+    run: fn(&str, &'static Node) -> String,
+) -> String {
+    if paths.is_empty() {
+        return format!("{command}: missing operand");
+    }
+
+    paths
+        .iter()
+        .map(|path| match vfs::resolve(path) {
+            Some(node) if !sudo && node.page == Some(vfs::HIDDEN_PAGE) => {
+                format!("{command}: {path}: Permission denied")
+            }
+            Some(node) => run(path, node),
+            None => format!("{command}: {path}: No such file or directory"),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn cat(path: &str, node: &'static Node) -> String {
+    match node.kind {
+        Kind::Directory(_) => format!("cat: {path}: Is a directory"),
+        Kind::Text(text) => text.to_string(),
+        Kind::Generated { .. } => format!("cat: {path}: Made up on the fly, open it from the menu"),
+        Kind::Binary { .. } => {
+            "\u{7f}ELF\u{2}\u{1}\u{1}   >  @  8 @ \u{fffd}\u{fffd}\u{fffd}".to_string()
+        }
+    }
+}
+
+fn file(path: &str, node: &'static Node) -> String {
+    let description = match node.kind {
+        Kind::Directory(_) => "directory",
+        Kind::Text(text) if text.is_ascii() => "ASCII text",
+        Kind::Text(_) | Kind::Generated { .. } => "Unicode text, UTF-8 text",
+        Kind::Binary { description, .. } => description,
+    };
+
+    let long_lines = match node.kind {
+        Kind::Text(text) => text.lines().any(|line| line.len() > 300),
+        _ => false,
+    };
+
+    if long_lines {
+        format!("{path}: {description}, with very long lines")
+    } else {
+        format!("{path}: {description}")
+    }
+}
+
+fn ls(paths: &[&str], long: bool, all: bool) -> String {
+    let paths = if paths.is_empty() { &["."][..] } else { paths };
+
+    paths
+        .iter()
+        .map(|path| {
+            let Some(node) = vfs::resolve(path) else {
+                return format!("ls: cannot access '{path}': No such file or directory");
+            };
+
+            let listing = if node.is_directory() {
+                list_directory(node, long, all)
+            } else if long {
+                long_line(node, path)
+            } else {
+                path.to_string()
+            };
+
+            if paths.len() > 1 {
+                format!("{path}:\n{listing}")
+            } else {
+                listing
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// The children of a directory in the order `ls` and `tree` print them,
+/// dots ignored and case folded.
+fn listed(directory: &'static Node, all: bool) -> Vec<&'static Node> {
+    let mut children: Vec<&Node> = directory
+        .children()
+        .iter()
+        .filter(|node| all || !node.is_hidden())
+        .collect();
+
+    children.sort_by_key(|node| node.name.trim_start_matches('.').to_lowercase());
+    children
+}
+
+fn list_directory(directory: &'static Node, long: bool, all: bool) -> String {
+    let mut entries: Vec<(&str, &Node)> = listed(directory, all)
+        .into_iter()
+        .map(|node| (node.name, node))
+        .collect();
+
+    if all {
+        entries.insert(0, ("..", directory));
+        entries.insert(0, (".", directory));
+    }
+
+    if !long {
+        return entries
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join("  ");
+    }
+
+    let blocks: usize = entries
+        .iter()
+        .map(|(_, node)| node.size().div_ceil(4096) * 4)
+        .sum();
+    let width = entries
+        .iter()
+        .map(|(_, node)| node.size().to_string().len())
+        .max()
+        .unwrap_or(1);
+
+    let mut lines = vec![format!("total {blocks}")];
+    lines.extend(entries.iter().map(|(name, node)| {
+        format!(
+            "{} {:>2} {user} {user} {:>width$} {} {name}",
+            node.mode,
+            links(node),
+            node.size(),
+            node.modified,
+            user = vfs::USER,
+        )
+    }));
+    lines.join("\n")
+}
+
+fn long_line(node: &Node, path: &str) -> String {
+    format!(
+        "{} {} {user} {user} {} {} {path}",
+        node.mode,
+        links(node),
+        node.size(),
+        node.modified,
+        user = vfs::USER,
+    )
+}
+
+/// Hard links: a directory is linked from its parent, from its own `.` and
+/// from the `..` of each subdirectory.
+fn links(node: &Node) -> usize {
+    if node.is_directory() {
+        2 + node
+            .children()
+            .iter()
+            .filter(|child| child.is_directory())
+            .count()
+    } else {
+        1
+    }
+}
+
+fn tree(path: &str, all: bool) -> String {
+    let Some(node) = vfs::resolve(path) else {
+        return format!("{path} [error opening dir]\n\n0 directories, 0 files");
+    };
+
+    let mut lines = vec![path.to_string()];
+    let (mut directories, mut files) = (0, 0);
+    branch(node, "", all, &mut lines, &mut directories, &mut files);
+
+    lines.push(String::new());
+    lines.push(format!(
+        "{directories} {}, {files} {}",
+        if directories == 1 {
+            "directory"
+        } else {
+            "directories"
+        },
+        if files == 1 { "file" } else { "files" },
+    ));
+    lines.join("\n")
+}
+
+fn branch(
+    node: &'static Node,
+    prefix: &str,
+    all: bool,
+    lines: &mut Vec<String>,
+    directories: &mut usize,
+    files: &mut usize,
+) {
+    let children = listed(node, all);
+
+    for (index, child) in children.iter().enumerate() {
+        let last = index + 1 == children.len();
+        lines.push(format!(
+            "{prefix}{}{}",
+            if last { "└── " } else { "├── " },
+            child.name
+        ));
+
+        if child.is_directory() {
+            *directories += 1;
+            let prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+            branch(child, &prefix, all, lines, directories, files);
+        } else {
+            *files += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text;
+
+    #[test]
+    fn the_history_is_listed_but_unreadable_without_sudo() {
+        assert!(!run("ls", false).contains(".bash_history"));
+        assert!(run("ls -a", false).contains(".bash_history"));
+        assert!(run("tree -a", false).contains(".bash_history"));
+
+        assert_eq!(
+            run("cat .bash_history", false),
+            "cat: .bash_history: Permission denied"
+        );
+        assert_eq!(
+            run("file ~/.bash_history", false),
+            "file: ~/.bash_history: Permission denied"
+        );
+    }
+
+    #[test]
+    fn sudo_opens_the_history() {
+        assert_eq!(run("cat .bash_history", true), text::BASH_HISTORY);
+        assert!(run("file .bash_history", true).starts_with(".bash_history: "));
+    }
+
+    #[test]
+    fn cat_and_file_answer_for_every_path() {
+        assert_eq!(
+            run("cat games nothing", false),
+            "cat: games: Is a directory\ncat: nothing: No such file or directory"
+        );
+        assert_eq!(run("file games", false), "games: directory");
+        assert_eq!(run("cat", false), "cat: missing operand");
+    }
+
+    #[test]
+    fn ls_and_tree_walk_the_tree() {
+        assert_eq!(run("ls games", false), "2048  snake  tetris");
+        assert_eq!(
+            run("ls nothing", false),
+            "ls: cannot access 'nothing': No such file or directory"
+        );
+        assert_eq!(
+            run("tree games", false),
+            "games\n├── 2048\n├── snake\n└── tetris\n\n0 directories, 3 files"
+        );
+        assert_eq!(run("whoami", false), vfs::USER);
+        assert_eq!(run("rm -rf /", false), "bash: rm: command not found");
+    }
+}
@@ -0,0 +1,383 @@
+//! The personal soundtrack as structured data.
+//!
+//! Albums are rendered as a table that can be sorted, filtered by genre,
+//! grouped by artist, and asked for a random pick.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::Line,
+    widgets::{
+        Cell, HighlightSpacing, Paragraph, Row, StatefulWidget, Table, TableState, Widget, Wrap,
+    },
+};
+
+use crate::{random::Rng, text};
+
+#[derive(Debug)]
+pub struct Album {
+    pub artist: &'static str,
+    pub title: &'static str,
+    pub year: Option<u16>,
+    pub genres: &'static [&'static str],
+    pub link: Option<&'static str>,
+}
+
+const fn album(
+    artist: &'static str,
+    title: &'static str,
+    year: Option<u16>,
+    genres: &'static [&'static str],
+) -> Album {
+    Album {
+        artist,
+        title,
+        year,
+        genres,
+        link: None,
+    }
+}
+
+/// Whether any album has a link, given as `Album { link: Some(..),
+/// ..album(..) }`. The link column is left out otherwise.
+const HAS_LINKS: bool = {
+    let mut index = 0;
+    let mut found = false;
+    while index < ALBUMS.len() {
+        found |= ALBUMS[index].link.is_some();
+        index += 1;
+    }
+    found
+};
+
+#[rustfmt::skip]
+pub const ALBUMS: &[Album] = &[
+    album("Rotting Christ", "Theogonia", Some(2007), &["black metal"]),
+    album("Rotting Christ", "Triarchy of the Lost Lovers", Some(1996), &["black metal", "gothic"]),
+    album("Thou Art Lord", "DV6", Some(2005), &["black metal"]),
+    album("Moonspell", "Wolfheart", Some(1995), &["gothic metal"]),
+    album("Moonspell", "Night Eternal", Some(2008), &["gothic metal"]),
+    album("Moonspell", "Sin/Pecado", Some(1998), &["gothic metal"]),
+    album("Septicflesh", "Revolution DNA", Some(1999), &["gothic metal"]),
+    album("Septicflesh", "Communion", Some(2008), &["death metal", "symphonic"]),
+    album("Nightfall", "Diva Futura", Some(1999), &["gothic metal"]),
+    album("The Kovenant", "Animatronik", Some(1999), &["industrial", "metal"]),
+    album("Ominous Grief", "Nothing In Remembrance", None, &["gothic metal"]),
+    album("Type O Negative", "Life Is Killing Me", Some(2003), &["gothic metal"]),
+    album("Type O Negative", "Dead Again", Some(2007), &["gothic metal"]),
+    album("Tiamat", "Prey", Some(2003), &["gothic metal"]),
+    album("Samael", "Reign of Light", Some(2004), &["industrial", "metal"]),
+    album("Kurban", "Yobaz", None, &["rock"]),
+    album("Arkona", "Goi, Rode, Goi!", Some(2009), &["folk", "metal"]),
+    album("Einstürzende Neubauten", "Alles in Allem", Some(2020), &["industrial"]),
+    album("Mindless Faith", "Manifest Destiny", None, &["industrial"]),
+    album("Sirenia", "An Elixir for the Existence", Some(2004), &["gothic metal"]),
+    album("Sirenia", "At Sixes and Sevens", Some(2002), &["gothic metal"]),
+    album("Threatre of Tragedy", "Aegis", Some(1998), &["gothic metal"]),
+    album("Tristania", "Widow's Weeds", Some(1998), &["gothic metal"]),
+    album("Siouxsie & The Banshees", "Kaleidoscope", Some(1980), &["post-punk"]),
+    album("Sisters Of Mercy", "Floodlang", Some(1987), &["gothic rock"]),
+    album("Velvet Acid Christ", "The Art of Breaking Apart", Some(2006), &["industrial"]),
+    album("Angelspit", "100%", None, &["industrial"]),
+    album("Skinny Puppy", "Remission", Some(1984), &["industrial"]),
+    album("Skinny Puppy", "Process", Some(1996), &["industrial"]),
+    album("Fad Gadget", "Gag", Some(1984), &["synth-pop"]),
+    album("En Esch", "Cheesy", Some(1993), &["industrial"]),
+    album("mind.in.a.box", "Dreamweb", Some(2005), &["electronic"]),
+    album("Krystal System", "Underground", None, &["industrial"]),
+    album("Collide", "Chasing the Ghost", Some(2000), &["industrial", "darkwave"]),
+    album("The Secret Meeting", "Ultrashiver", None, &["industrial"]),
+    album("Android Lust", "The Divide", None, &["industrial", "darkwave"]),
+    album("Russkaja", "Energia", Some(2013), &["folk", "ska"]),
+    album("CCCP Fedeli Alla Linea", "Epica Etnica Etnica Pathos", Some(1990), &["punk"]),
+    album("Faun", "Eden", Some(2011), &["folk"]),
+    album("Penumbra", "Seclusion", None, &["gothic metal"]),
+    album("Neurotech", "Antagonist", None, &["industrial", "metal"]),
+    album("Gentle Giant", "In a Glass House", Some(1973), &["progressive"]),
+    album("Magma", "Mekanik destruktiw kommandoh", Some(1973), &["progressive"]),
+    album("Eisenfunk", "8-Bit", None, &["electronic"]),
+];
+
+/// Remarks shown on the group header of an artist.
+const ARTIST_NOTES: &[(&str, &str)] = &[(
+    "Rotting Christ",
+    "I can list every Rotting Christ album except for the last one",
+)];
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Sort {
+    #[default]
+    Unordered,
+    Artist,
+    Title,
+    Year,
+}
+
+impl Sort {
+    const fn next(self) -> Self {
+        match self {
+            Sort::Unordered => Sort::Artist,
+            Sort::Artist => Sort::Title,
+            Sort::Title => Sort::Year,
+            Sort::Year => Sort::Unordered,
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Sort::Unordered => "no particular order",
+            Sort::Artist => "artist",
+            Sort::Title => "album",
+            Sort::Year => "year",
+        }
+    }
+}
+
+/// A row of the table, either a group header or an album.
+#[derive(Debug, Clone, Copy)]
+enum Entry {
+    Artist(&'static str, usize),
+    Album(&'static Album),
+}
+
+#[derive(Debug, Default)]
+pub struct Soundtrack {
+    sort: Sort,
+    genre: Option<&'static str>,
+    grouped: bool,
+    pick: Option<&'static Album>,
+    table_state: TableState,
+    rng: Rng,
+}
+
+impl Soundtrack {
+    fn genres() -> Vec<&'static str> {
+        let mut genres: Vec<&str> = ALBUMS
+            .iter()
+            .flat_map(|album| album.genres.iter().copied())
+            .collect();
+        genres.sort_unstable();
+        genres.dedup();
+        genres
+    }
+
+    fn albums(&self) -> Vec<&'static Album> {
+        let mut albums: Vec<&Album> = ALBUMS
+            .iter()
+            .filter(|album| self.genre.is_none_or(|genre| album.genres.contains(&genre)))
+            .collect();
+
+        match self.sort {
+            Sort::Unordered => {}
+            Sort::Artist => albums.sort_by_key(|album| album.artist.to_lowercase()),
+            Sort::Title => albums.sort_by_key(|album| album.title.to_lowercase()),
+            Sort::Year => albums.sort_by_key(|album| (album.year.is_none(), album.year)),
+        }
+
+        if self.grouped {
+            // A stable sort keeps the chosen order within each artist.
+            albums.sort_by_key(|album| album.artist.to_lowercase());
+        }
+
+        albums
+    }
+
+    fn entries(&self) -> Vec<Entry> {
+        let albums = self.albums();
+
+        if !self.grouped {
+            return albums.into_iter().map(Entry::Album).collect();
+        }
+
+        let mut entries = Vec::new();
+        for (index, album) in albums.iter().enumerate() {
+            if index == 0 || albums[index - 1].artist != album.artist {
+                let count = albums
+                    .iter()
+                    .filter(|other| other.artist == album.artist)
+                    .count();
+                entries.push(Entry::Artist(album.artist, count));
+            }
+            entries.push(Entry::Album(album));
+        }
+        entries
+    }
+
+    pub fn select_previous(&mut self, rows: u16) {
+        let selected = self.table_state.selected().unwrap_or_default();
+        self.table_state
+            .select(Some(selected.saturating_sub(rows.into())));
+    }
+
+    pub fn select_next(&mut self, rows: u16) {
+        let last = self.entries().len().saturating_sub(1);
+        let selected = self.table_state.selected().unwrap_or_default();
+        self.table_state
+            .select(Some((selected + usize::from(rows)).min(last)));
+    }
+
+    pub fn select_first(&mut self) {
+        self.table_state.select(Some(0));
+    }
+
+    pub fn select_last(&mut self) {
+        self.table_state
+            .select(Some(self.entries().len().saturating_sub(1)));
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.table_state.selected().unwrap_or_default() + 1 >= self.entries().len()
+    }
+
+    pub fn next_sort(&mut self) {
+        self.sort = self.sort.next();
+        self.select_first();
+    }
+
+    /// Cycles through every genre, then back to showing everything.
+    pub fn next_genre(&mut self) {
+        let genres = Soundtrack::genres();
+
+        self.genre = match self.genre {
+            None => genres.first().copied(),
+            Some(genre) => genres
+                .iter()
+                .position(|other| *other == genre)
+                .and_then(|index| genres.get(index + 1))
+                .copied(),
+        };
+        self.select_first();
+    }
+
+    pub fn toggle_grouped(&mut self) {
+        self.grouped = !self.grouped;
+        self.select_first();
+    }
+
+    pub fn pick_random(&mut self) {
+        let entries = self.entries();
+        let albums: Vec<(usize, &'static Album)> = entries
+            .iter()
+            .enumerate()
+            .filter_map(|(index, entry)| match entry {
+                Entry::Album(album) => Some((index, *album)),
+                Entry::Artist(..) => None,
+            })
+            .collect();
+
+        if albums.is_empty() {
+            return;
+        }
+
+        let (index, album) = albums[self.rng.below(albums.len())];
+        self.pick = Some(album);
+        self.table_state.select(Some(index));
+    }
+
+    fn row(entry: Entry, pick: Option<&'static Album>) -> Row<'static> {
+        match entry {
+            Entry::Artist(artist, count) => {
+                let note = ARTIST_NOTES
+                    .iter()
+                    .find(|(noted, _)| *noted == artist)
+                    .map_or(String::new(), |(_, note)| format!(" ({note})"));
+
+                Row::new([Cell::from(format!(
+                    "{artist}: {count} album{}{note}",
+                    if count == 1 { "" } else { "s" }
+                ))])
+                .style(
+                    Style::default()
+                        .add_modifier(Modifier::BOLD)
+                        .fg(Color::LightMagenta),
+                )
+            }
+            Entry::Album(album) => {
+                let mut cells = vec![
+                    Cell::from(album.artist),
+                    Cell::from(album.title),
+                    Cell::from(album.year.map_or("-".into(), |year| year.to_string())),
+                    Cell::from(album.genres.join(", ")),
+                ];
+                if HAS_LINKS {
+                    cells.push(Cell::from(album.link.unwrap_or_default()).light_blue());
+                }
+                let row = Row::new(cells);
+
+                if pick.is_some_and(|pick| std::ptr::eq(pick, album)) {
+                    row.fg(Color::LightYellow)
+                } else {
+                    row
+                }
+            }
+        }
+    }
+
+    pub fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let intro = Paragraph::new(
+            text::MUSIC
+                .split('\n')
+                .map(|line| match line.starts_with(">>>") {
+                    true => Line::from(line).bold(),
+                    false => Line::from(line),
+                })
+                .collect::<Vec<_>>(),
+        )
+        .wrap(Wrap { trim: true });
+        let intro_height = (intro.line_count(area.width) as u16).min(area.height / 2);
+
+        let [intro_area, table_area, status_area, footer_area] = Layout::vertical([
+            Constraint::Length(intro_height),
+            Constraint::Fill(1),
+            Constraint::Length(2),
+            Constraint::Length(3),
+        ])
+        .areas(area);
+
+        intro.render(intro_area, buf);
+
+        let rows: Vec<Row> = self
+            .entries()
+            .into_iter()
+            .map(|entry| Soundtrack::row(entry, self.pick))
+            .collect();
+
+        let mut widths = vec![
+            Constraint::Fill(2),
+            Constraint::Fill(3),
+            Constraint::Length(4),
+            Constraint::Fill(2),
+        ];
+        let mut header = vec!["artist", "album", "year", "genres"];
+        if HAS_LINKS {
+            widths.push(Constraint::Fill(1));
+            header.push("link");
+        }
+
+        let table = Table::new(rows, widths)
+            .header(Row::new(header).bold().underlined())
+            .column_spacing(2)
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_spacing(HighlightSpacing::Never);
+
+        if self.table_state.selected().is_none() {
+            self.table_state.select(Some(0));
+        }
+        StatefulWidget::render(table, table_area, buf, &mut self.table_state);
+
+        let mut status = vec![format!("sorted by {}", self.sort.name())];
+        if let Some(genre) = self.genre {
+            status.push(format!("only {genre}"));
+        }
+        if self.grouped {
+            status.push("grouped by artist".into());
+        }
+        if let Some(pick) = self.pick {
+            status.push(format!("tonight's pick: {} - {}", pick.artist, pick.title));
+        }
+
+        Paragraph::new(Line::from(format!("~   {}", status.join(" | ")))).render(status_area, buf);
+        Paragraph::new(text::MUSIC_FOOTER).render(footer_area, buf);
+    }
+}
@@ -0,0 +1,20 @@
+//! Tiny wrapper around the browser's `localStorage`.
+//!
+//! Everything here is best effort: private windows and strict privacy
+//! settings can deny storage, and the site should keep working anyway.
+
+const PREFIX: &str = "tarbetu.";
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok().flatten()
+}
+
+pub fn get(key: &str) -> Option<String> {
+    local_storage()?.get_item(&format!("{PREFIX}{key}")).ok()?
+}
+
+pub fn set(key: &str, value: &str) {
+    if let Some(storage) = local_storage() {
+        let _ = storage.set_item(&format!("{PREFIX}{key}"), value);
+    }
+}
@@ -57,6 +57,13 @@ pub const NAME3: &str = "
          ░     ░           ░              ░  ░   ░              
 ";
 
+/// The intro frames on screens too small for the art: first drawn with the
+/// banner font, then as plain text.
+pub const INTRO_NAME_BANNER: &str = "Emrecan\nŞuşter";
+pub const INTRO_NAME: &str = "Emrecan Şuşter";
+pub const PRESS_ANY_KEY_BANNER: &str = "any key";
+pub const PRESS_ANY_KEY_TEXT: &str = "hit any key to continue";
+
 pub const PRESS_ANY_KEY: &str = r#"
              _
              | |
@@ -77,22 +84,23 @@ pub const PRESS_ANY_KEY: &str = r#"
      hit any key to continue or wait a little (not mobile friendly)
 "#;
 
-pub const TARBETU: &str = "01010100 01100001 01110010 01100010 01100101 01110100 01110101";
-pub const TARBETU1: &str = "   T    01100001 01110010 01100010 01100101 01110100 01110101";
-pub const TARBETU2: &str = "   T        A    01110010 01100010 01100101 01110100 01110101";
-pub const TARBETU3: &str = "   T        A        R    01100010 01100101 01110100 01110101";
-pub const TARBETU4: &str = "   T        A        R        B    01100101 01110100 01110101";
-pub const TARBETU5: &str = "   T        A        R        B        E    01110100 01110101";
-pub const TARBETU6: &str = "   T        A        R        B        E        T    01110101";
-pub const TARBETU7: &str =
-    "   T        A        R        B        E        T        U   - Ya bütün sırrı bozdun";
-
-pub const ABOUT: &str =
-">>> ./tarbetu
+/// The word hidden in the binary header, typed letter by letter to decode it.
+pub const TITLE_WORD: &str = "Tarbetu";
+/// Shown next to the header once every letter is decoded.
+pub const TITLE_MESSAGE: &str = "Ya bütün sırrı bozdun";
+/// One of `binary`, `hex` or `base64`.
+pub const TITLE_ENCODING: &str = "binary";
+/// The FIGlet font of the `./tarbetu` banner, one of `mini` or `block`.
+pub const BANNER_FONT: &str = "mini";
+
+/// What the about page shows before the `(gdb)` prompt; `bt` runs right away.
+pub const ABOUT: &str = ">>> ./tarbetu
 Segmentation fault (core dumped)
 >>> gdb ./tarbetu core
-(gdb) bt
-Name’s Emrecan Şuşter, in case that wasn’t painfully obvious from whatever intro came before this. I’m the boring computer guy — the one who talks about compilers, type systems, and other stuff you probably couldn’t care less about.
+For help, type \"help\".";
+
+pub const GDB_BACKTRACE: &str =
+"Name’s Emrecan Şuşter, in case that wasn’t painfully obvious from whatever intro came before this. I’m the boring computer guy — the one who talks about compilers, type systems, and other stuff you probably couldn’t care less about.
 
 Once upon a time, I tried to become a sci-fi author. Turns out publishers don’t give a damn about cyberpunk dystopias or whatever weird political stuff could go down in Cyprus and Turkey. Pff. Their loss. (That novel took me ten years, by the way.)
 
@@ -102,45 +110,152 @@ Now I’m building my own programming language. Why? Because apparently I have a
 
 Still, I like to pretend I’m a social creature. You’ll find me coding at some cozy café in Kadıköy, ordering a filter coffee like it’s a personality trait. It’s the best way to simulate human interaction without actually being annoying.
 
-So yeah — it’s more fun to compute.
-(gdb) info registers
-rax            0x31313131          // main routine
-rbx            0xBAADCAFE          // caffeine
-rcx            0xDEADBEAF          // dreams
-rip            0x0                 // hope
-(gdb) print motivation
+So yeah — it’s more fun to compute.";
+
+pub const GDB_REGISTERS: &str = "| Register | Value      | Holds        |
+|:---------|-----------:|:-------------|
+| rax      | 0x31313131 | main routine |
+| rbx      | 0xBAADCAFE | caffeine     |
+| rcx      | 0xDEADBEAF | dreams       |
+| rip      | 0x0        | hope         |";
+
+pub const GDB_MOTIVATION: &str =
+"```bar raw
 $1 = {linux=1, ruby=2, rust=3, cpp=1, erlang=2, elixir=3, mlir=1, llvm=1, frontend=0, code_for_food=1, capitalism=0}
-(gdb) info links
-https://github.com/Tarbetu
-https://linkedin.com/in/emrecan-suster
-(gdb) quit
->>> echo $?
+```";
+
+pub const GDB_LINKS: &str = "https://github.com/Tarbetu
+https://linkedin.com/in/emrecan-suster";
+
+pub const GDB_QUIT: &str = ">>> echo $?
 126";
 
+pub const GDB_LIST: &str = "```c
+int main(void) {
+    while (coffee()) {
+        compile(dreams);
+    }
+
+    free(time);
+    return hope(); /* 0x0 */
+}
+```";
+
+pub const GDB_FRAME: &str = "#0  0x0000000000000000 in hope () at tarbetu.c:126
+126         return hope(); /* 0x0 */";
+
+pub const GDB_HELP: &str = "List of commands:
+
+| Command          | Description                                      |
+|:-----------------|:-------------------------------------------------|
+| backtrace, bt    | Print backtrace of all stack frames.             |
+| frame, f         | Select and print a stack frame.                  |
+| info registers   | List of integer registers and their contents.    |
+| info links       | Where else to find the process.                  |
+| print motivation | Print the value of an expression.                |
+| list, l          | List specified function or line.                 |
+| shell, !         | Execute the rest of the line as a shell command. |
+| help, h          | Print list of commands.                          |
+| quit, q          | Exit gdb.                                        |";
+
 pub const PORTFOLIO: &str = r#">>> ./portfolio 
 Pretend that I changed the world with these. It'd make me happy.
 Also, I did stuff at work. Secret, NDA, corporate mystery. Move on.
 
-----------------------------------------------------
+- ./portfolio/website
+This very site. A glorified README with styling.
+- ./portfolio/tarlox
+A Lox interpreter that went further than the book meant it to.
+- ./portfolio/ducktongue
+A command-line dictionary for language learners, written in Elixir.
+
+Unfold ./portfolio in the menu to read about each of them.
+
+----------------------------------------------------"#;
 
-- This website
-Handmade with Ratatui, served cold by Ratzilla.
+pub const PORTFOLIO_WEBSITE: &str = r#">>> ./portfolio/website
+This website. Handmade with Ratatui, served cold by Ratzilla.
 It's basically a glorified README with styling.
+```rust
+let backend = DomBackend::new()?;
+let terminal = Terminal::new(backend)?;
+
+terminal.draw_web(move |frame| app.borrow_mut().render(frame)); // that's the whole trick
+```
 If you're curious about the monstrosity:
-https://github.com/Tarbetu/website
+https://github.com/Tarbetu/website"#;
 
-- Tarlox
+pub const PORTFOLIO_TARLOX: &str = r#">>> ./portfolio/tarlox
 The obligatory "Crafting Interpreters" clone gone rogue. I tried to every nasty thing can done with a programming language. There are some interesting features:
     --- Memoization of calculated values
     --- Variables calculated without blocking main thread
     --- Tail recursion
-https://github.com/Tarbetu/tarlox
-
-- DuckTongue
+```lox
+fun fib(n) {
+  if (n < 2) return n;
+  return fib(n - 1) + fib(n - 2); // calculated once, remembered afterwards
+}
+
+print fib(90);
+```
+No need to take my word for it: the playground below runs a pocket Tarlox right in the page. Lock in, press Enter to start typing, then type :examples and :load one of them.
+https://github.com/Tarbetu/tarlox"#;
+
+pub const LOX_BANNER: &str = "pocket tarlox: Lox without classes, running in your browser
+:examples lists the showcases, :load <name> puts one in the input";
+
+pub const LOX_EXAMPLES: &[(&str, &str)] = &[
+    (
+        "memoization",
+        r#"// fib is pure, so every fib(n) is computed once and remembered.
+// Without that, fib(90) would take about 10^18 calls.
+fun fib(n) {
+  if (n < 2) return n;
+  return fib(n - 1) + fib(n - 2);
+}
+
+print fib(90);"#,
+    ),
+    (
+        "non-blocking",
+        r#"// A global initialized with a call doesn't hold up the lines after it.
+fun slow(n) {
+  var total = 0;
+  for (var i = 1; i <= n; i = i + 1) total = total + i;
+  print "...slow() is done";
+  return total;
+}
+
+var answer = slow(100000);
+print "answer is declared, moving on";
+print answer;"#,
+    ),
+    (
+        "tail-recursion",
+        r#"// return f(...) reuses the frame, so this goes a hundred thousand deep.
+fun sum(n, total) {
+  if (n == 0) return total;
+  return sum(n - 1, total + n);
+}
+
+print sum(100000, 0);
+
+// The addition waits for the call here, so the stack overflows.
+fun naive(n) {
+  if (n == 0) return 0;
+  return n + naive(n - 1);
+}
+
+print naive(100000);"#,
+    ),
+];
+
+pub const PORTFOLIO_DUCKTONGUE: &str = r#">>> ./portfolio/ducktongue
 Elixir is sexy, and I had to try something. This is an CLI-based simple dictionary application for language learners. Also it has a JSON API so you can get them with TCP. The missing part is the words. Does it do anything cool? No? But it's mine.
-https://github.com/Tarbetu/DuckTongue
+Below is a pocket DuckTongue with a handful of words. Lock in and press Enter to search, in English or Turkish, typos welcome. ↑ and ↓ pick a word, and :endpoint <url> points Enter at a real DuckTongue server.
+https://github.com/Tarbetu/DuckTongue"#;
 
-- ./translations
+pub const PORTFOLIO_SEE_ALSO: &str = r#"- ./translations
 I have translated some technical docs to Turkish. Because apparently I care about knowledge accessibility."#;
 
 pub const TRANSLATIONS: &str = r#">>> ./translations
@@ -148,10 +263,19 @@ I didn't do some serious translations or someone hasn't translated me yet. Howev
 
 Türkçe kaynakların sayısı biraz az, ondan dolayı en azından ufak da olsa katkıda bulunmak adına iki kitabı çevirdim. Okuyup "Allah razı olsun" mu dersiniz size bırakıyorum. Gördüğünüz bir hata olursa istediğiniz yerden ulaşın, ya da direkt ilgili GitHub sayfasına issue açın. Çeviride bazı hatalar var, kabul.
 
+- ./translations/gentle-intro
+Rust'a Yumuşak Bir Başlangıç
+- ./translations/rpm_paketleme
+RPM Paketleme Rehberi
+
+Kitaplar için menüde ./translations dalını açın."#;
+
+pub const TRANSLATION_GENTLE_INTRO: &str = r#">>> ./translations/gentle-intro
 - Rust'a Yumuşak Bir Başlangıç
 https://tarbetu.github.io/gentle-intro/
-Rust'a başlamak için bence güzel bir kitap. Tüm Rust'ı buradan özümseyemezsiniz. Biraz eskisi doğrusu. Ancak Rust nedir, ne yapar, yenir mi bu konularda güzel bir yönlendirme yapabilir.
+Rust'a başlamak için bence güzel bir kitap. Tüm Rust'ı buradan özümseyemezsiniz. Biraz eskisi doğrusu. Ancak Rust nedir, ne yapar, yenir mi bu konularda güzel bir yönlendirme yapabilir."#;
 
+pub const TRANSLATION_RPM: &str = r#">>> ./translations/rpm_paketleme
 - RPM Paketleme Rehberi
 https://tarbetu.github.io/rpm_paketleme/
 Fedora kullandınız mı? OpenSUSE? Red Hat diyince ne çağrışım yapıyor? Yapmıyorsa önemli değil. Yapıyorsa haberiniz olsun, RPM paketleme nasıl yapılıyor kafanız karışıyorsa buradan bir bakabilirsiniz.
@@ -160,7 +284,12 @@ Fedora kullandınız mı? OpenSUSE? Red Hat diyince ne çağrışım yapıyor? Y
 pub const LYCIAN_PROJECT: &str = ">>> ./lycian
 Lycian is a purely functional, lazy, memoized, and object-oriented programming language that doesn’t believe in variables, but does believe in doing strange things with compilers. Designed around deterministic behavior, literal-type inference, and automatic parallelism, Lycian treats all computation as a graph of pure function calls — no side effects, no surprises, no mercy. Inspired by Haskell, shaped in Elixir, and named after the ancient region of Lycia, it aims to answer a simple question: what if functional purity and OOP stopped fighting and got weird together?
 
-However, this project isn't done yet and it would be shame to make it public. Thank you for your understanding.";
+However, this project isn't done yet and it would be shame to make it public. Thank you for your understanding.
+
+Until then, there's a pocket Lisp below to keep your hands busy. Lock in and press Enter to type away; unbalanced parentheses carry on to the next line.";
+
+pub const LISP_BANNER: &str = "pocket lisp, a stand-in until Lycian grows up
+try (define (square x) (* x x)) and then (square 12)";
 
 pub const MUSIC: &str = ">>> ./personal_soundtrack | less
 Don't get me wrong, I'm not a producer. So this list isn't a collection of what have I done.
@@ -170,62 +299,11 @@ My only relation with art is just listening to some music, so this is all the th
 These are my favorite albums, without any ordering.
 Bit of Rock, bit of Metal, bit of Industrial.
 So don't look for something decent. But hey...
-This is my personal website so I can list anything I want.
-
-╭────────────────────────────────────────────────────╮
-│   PERSONAL SOUNDTRACK DUMP (no particular order)   │
-╰────────────────────────────────────────────────────╯
-
-Rotting Christ - Theogonia 
-Rotting Christ - Triarchy of the Lost Lovers
-Thou Art Lord - DV6
-(I can list every Rotting Christ album except for the last one)
-Moonspell - Wolfheart
-Moonspell - Night Eternal
-Moonspell - Sin/Pecado
-Septicflesh - Revolution DNA
-Septicflesh - Communion
-Nightfall - Diva Futura
-The Kovenant - Animatronik
-Ominous Grief - Nothing In Remembrance
-Type O Negative - Life Is Killing Me
-Type O Negative - Dead Again
-Tiamat - Prey
-Samael - Reign of Light
-Kurban - Yobaz
-Arkona - Goi, Rode, Goi!
-Einstürzende Neubauten - Alles in Allem
-Mindless Faith - Manifest Destiny
-Sirenia - An Elixir for the Existence 
-Sirenia - At Sixes and Sevens
-Threatre of Tragedy - Aegis
-Tristania - Widow's Weeds
-Siouxsie & The Banshees - Kaleidoscope
-Sisters Of Mercy - Floodlang
-Velvet Acid Christ - The Art of Breaking Apart
-Angelspit - 100%
-Skinny Puppy - Remission
-Skinny Puppy - Process
-Fad Gadget - Gag
-En Esch - Cheesy
-mind.in.a.box - Dreamweb
-Krystal System - Underground
-Collide - Chasing the Ghost
-The Secret Meeting - Ultrashiver
-Android Lust - The Divide
-Russkaja - Energia
-CCCP Fedeli Alla Linea - Epica Etnica Etnica Pathos
-Faun - Eden
-Penumbra - Seclusion
-Neurotech - Antagonist
-Gentle Giant - In a Glass House
-Magma - Mekanik destruktiw kommandoh
-Eisenfunk - 8-Bit
-~  
-~   Press esc to quit
-~   All tracks play best after 2AM with headphones on.  
-~  
-";
+This is my personal website so I can list anything I want.";
+
+pub const MUSIC_FOOTER: &str = "~   Press esc to quit
+~   All tracks play best after 2AM with headphones on.
+~";
 
 pub const ECHOES: &str = r#">>> ./echoes_from_my_mania
 Echoes From My Mania is my blog, and it's not strictly about programming. I mostly write about whatever I've been thinking lately.
@@ -249,3 +327,23 @@ English:
 https://emrecansuster.com/hierarchyofvulponoir/
 Türkçe:  
 https://emrecansuster.com/karatilkihiyerarsisi/";
+
+pub const HJKL_FOUND: &str = "All four at once? Relax, there is no file to save here.";
+
+pub const SECRET_FOUND: &str =
+    "You decoded the whole title. Congratulations, now the secret is ruined for everyone.";
+
+pub const BASH_HISTORY: &str = ">>> cat ./.bash_history
+Well, you typed sudo. Nobody ever told you that it works here, but here we are.
+
+cd ~/projects/lycian
+cargo build --release
+git commit -m \"fix\"
+git commit -m \"fix again\"
+git commit -m \"please\"
+git push --force
+man gdb
+gdb ./tarbetu core
+sudo make me a sandwich
+history | grep coffee
+exit";
@@ -0,0 +1,305 @@
+//! The header of the list view.
+//!
+//! The title is a word rendered byte by byte in some encoding. Every letter
+//! the visitor decodes flips its bits for a moment before settling into the
+//! letter itself, and the whole header scrambles into place when the list
+//! view first shows up.
+
+use ratatui::{
+    style::{Color, Stylize},
+    text::{Line, Span},
+};
+use web_time::{Duration, Instant};
+
+use crate::{
+    gradient::{Direction, Gradient},
+    random,
+};
+
+const FLIP_DURATION: Duration = Duration::from_millis(600);
+const SCRAMBLE_DURATION: Duration = Duration::from_millis(1200);
+const FRAME_DURATION: Duration = Duration::from_millis(50);
+const RAINBOW_PERIOD: Duration = Duration::from_secs(4);
+
+const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    #[default]
+    Binary,
+    Hex,
+    Base64,
+}
+
+impl Encoding {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "binary" => Some(Encoding::Binary),
+            "hex" => Some(Encoding::Hex),
+            "base64" => Some(Encoding::Base64),
+            _ => None,
+        }
+    }
+
+    fn encode(self, byte: u8) -> String {
+        match self {
+            Encoding::Binary => format!("{byte:08b}"),
+            Encoding::Hex => format!("{byte:02X}"),
+            Encoding::Base64 => [
+                BASE64[usize::from(byte >> 2)],
+                BASE64[usize::from((byte & 0b11) << 4)],
+            ]
+            .iter()
+            .map(|glyph| char::from(*glyph))
+            .collect(),
+        }
+    }
+
+    fn glyphs(self) -> &'static [u8] {
+        match self {
+            Encoding::Binary => b"01",
+            Encoding::Hex => b"0123456789ABCDEF",
+            Encoding::Base64 => BASE64,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Title {
+    letters: Vec<char>,
+    message: &'static str,
+    encoding: Encoding,
+    decoded_at: Vec<Option<Instant>>,
+    scrambled_at: Option<Instant>,
+    created_at: Instant,
+}
+
+impl Title {
+    pub fn new(word: &'static str, message: &'static str, encoding: Encoding) -> Self {
+        let letters: Vec<char> = word.chars().collect();
+
+        Self {
+            decoded_at: vec![None; letters.len()],
+            letters,
+            message,
+            encoding,
+            scrambled_at: None,
+            created_at: Instant::now(),
+        }
+    }
+
+    /// Marks the first `count` letters as decoded, animating the new ones.
+    pub fn decode(&mut self, count: usize, now: Instant) {
+        for (index, decoded_at) in self.decoded_at.iter_mut().enumerate() {
+            match (index < count, *decoded_at) {
+                (true, None) => *decoded_at = Some(now),
+                (false, Some(_)) => *decoded_at = None,
+                _ => {}
+            }
+        }
+    }
+
+    pub fn scramble(&mut self, now: Instant) {
+        self.scrambled_at = Some(now);
+    }
+
+    fn is_decoded(&self, now: Instant) -> bool {
+        self.decoded_at
+            .iter()
+            .all(|decoded_at| decoded_at.is_some_and(|at| now.duration_since(at) >= FLIP_DURATION))
+    }
+
+    fn cell(&self, letter: char) -> String {
+        let mut bytes = [0; 4];
+
+        letter
+            .encode_utf8(&mut bytes)
+            .bytes()
+            .map(|byte| self.encoding.encode(byte))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The letter shown once decoded, in capitals unless capitalizing it
+    /// takes more than one letter, as `ß` does, which would not fit.
+    fn decoded(letter: char) -> char {
+        let mut upper = letter.to_uppercase();
+
+        match (upper.next(), upper.next()) {
+            (Some(upper), None) => upper,
+            _ => letter,
+        }
+    }
+
+    fn random_glyph(&self, index: usize, position: usize, frame: u64) -> char {
+        let glyphs = self.encoding.glyphs();
+        let noise = random::noise2(index, position, frame) as usize;
+
+        char::from(glyphs[noise % glyphs.len()])
+    }
+
+    /// Whether a glyph has settled, given the progress of its animation.
+    fn settled(index: usize, position: usize, progress: f32) -> bool {
+        let threshold = (random::noise2(index, position, 0) % 1000) as f32 / 1000.0;
+        threshold < progress
+    }
+
+    pub fn line(&self, now: Instant) -> Line<'static> {
+        let frame =
+            (now.duration_since(self.created_at).as_millis() / FRAME_DURATION.as_millis()) as u64;
+
+        let mut spans = Vec::with_capacity(self.letters.len() * 2 + 1);
+
+        for (index, letter) in self.letters.iter().enumerate() {
+            if index > 0 {
+                spans.push(Span::from(" "));
+            }
+
+            let encoded = self.cell(*letter);
+            let width = encoded.chars().count();
+            let center = (width - 1) / 2;
+            let decoded = Title::decoded(*letter);
+
+            let cell = match self.decoded_at[index] {
+                Some(at) if now.duration_since(at) < FLIP_DURATION => {
+                    let progress =
+                        now.duration_since(at).as_secs_f32() / FLIP_DURATION.as_secs_f32();
+
+                    // One glyph per glyph of the encoded cell, so that the
+                    // cell keeps its width, spaces between bytes included.
+                    let flipping = encoded
+                        .chars()
+                        .enumerate()
+                        .map(
+                            |(position, glyph)| match Title::settled(index, position, progress) {
+                                true if position == center => decoded,
+                                true => ' ',
+                                false if glyph == ' ' => ' ',
+                                false => self.random_glyph(index, position, frame),
+                            },
+                        )
+                        .collect::<String>();
+
+                    flipping.light_magenta()
+                }
+                Some(_) => format!(
+                    "{}{decoded}{}",
+                    " ".repeat(center),
+                    " ".repeat(width - center - 1)
+                )
+                .into(),
+                None => match self.scrambled_at {
+                    Some(at) if now.duration_since(at) < SCRAMBLE_DURATION => {
+                        let progress =
+                            now.duration_since(at).as_secs_f32() / SCRAMBLE_DURATION.as_secs_f32();
+
+                        encoded
+                            .chars()
+                            .enumerate()
+                            .map(|(position, glyph)| {
+                                if glyph == ' ' || Title::settled(index, position, progress) {
+                                    glyph
+                                } else {
+                                    self.random_glyph(index, position, frame)
+                                }
+                            })
+                            .collect::<String>()
+                            .fg(Color::LightCyan)
+                    }
+                    _ => encoded.into(),
+                },
+            };
+
+            spans.push(cell);
+        }
+
+        if self.is_decoded(now) {
+            let message = Line::from(format!("- {}", self.message));
+            let rainbow = Gradient::rainbow(Direction::Horizontal)
+                .cycle(now.duration_since(self.created_at), RAINBOW_PERIOD);
+
+            spans.extend(rainbow.paint_line(message).spans);
+        }
+
+        Line::from(spans)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The text of every letter's cell, without the separators.
+    fn cells(title: &Title, now: Instant) -> Vec<String> {
+        title
+            .line(now)
+            .spans
+            .iter()
+            .step_by(2)
+            .take(title.letters.len())
+            .map(|span| span.content.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn letters_are_encoded_byte_by_byte() {
+        let title = Title::new("Tş", "", Encoding::Binary);
+        let now = Instant::now();
+
+        assert_eq!(cells(&title, now), ["01010100", "11000101 10011111"]);
+        assert_eq!(Title::new("T", "", Encoding::Hex).cell('T'), "54");
+        assert_eq!(Title::new("T", "", Encoding::Base64).cell('T'), "VA");
+    }
+
+    #[test]
+    fn decoding_marks_only_the_first_letters() {
+        let mut title = Title::new("abc", "", Encoding::Hex);
+        let now = Instant::now();
+
+        title.decode(2, now);
+        assert_eq!(title.decoded_at, [Some(now), Some(now), None]);
+
+        title.decode(1, now);
+        assert_eq!(title.decoded_at, [Some(now), None, None]);
+        assert!(!title.is_decoded(now + FLIP_DURATION));
+    }
+
+    #[test]
+    fn flips_keep_the_width_of_the_cell() {
+        let mut title = Title::new("€", "", Encoding::Binary);
+        let now = Instant::now();
+        title.decode(1, now);
+
+        for elapsed in [
+            0,
+            FLIP_DURATION.as_millis() as u64 / 2,
+            FLIP_DURATION.as_millis() as u64 - 1,
+        ] {
+            let cell = &cells(&title, now + Duration::from_millis(elapsed))[0];
+
+            assert_eq!(cell.chars().count(), "11100010 10000010 10101100".len());
+            assert_eq!(cell.chars().nth(17), Some(' '));
+        }
+    }
+
+    #[test]
+    fn flips_start_scrambled_and_end_on_the_letter() {
+        let mut title = Title::new("ş", "done", Encoding::Binary);
+        let now = Instant::now();
+        title.decode(1, now);
+
+        let start = &cells(&title, now)[0];
+        assert!(start.chars().all(|glyph| "01 ".contains(glyph)));
+
+        let end = now + FLIP_DURATION;
+        assert_eq!(cells(&title, end), ["        Ş        "]);
+        assert!(title.is_decoded(end));
+        assert!(title.line(end).to_string().ends_with("- done"));
+    }
+
+    #[test]
+    fn letters_without_a_single_capital_stay_as_they_are() {
+        assert_eq!(Title::decoded('ı'), 'I');
+        assert_eq!(Title::decoded('ß'), 'ß');
+    }
+}
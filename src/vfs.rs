@@ -0,0 +1,467 @@
+//! The filesystem behind the menu.
+//!
+//! Every menu entry is a node in the visitor's home directory, with fake
+//! permissions, sizes and modification times good enough for `ls -la`.
+//! The menu lists the home directory, with the subdirectories of a page as
+//! its subpages, the shell commands of the gdb prompt walk the tree, and
+//! routes like `./portfolio/tarlox` resolve through it to the page showing
+//! them.
+
+use crate::{menu::Entry, text};
+
+pub const USER: &str = "tarbetu";
+pub const HOME: &str = "/home/tarbetu";
+
+/// Directories are a block no matter what they hold.
+const DIRECTORY_SIZE: usize = 4096;
+
+/// Menu positions of the pages of the home directory. The hidden page
+/// comes last so that the menu can leave it out until `sudo` is found.
+pub const ABOUT_PAGE: usize = 0;
+pub const PORTFOLIO_PAGE: usize = 1;
+pub const TRANSLATIONS_PAGE: usize = 2;
+pub const LYCIAN_PAGE: usize = 3;
+pub const MUSIC_PAGE: usize = 4;
+pub const ECHOES_PAGE: usize = 5;
+pub const KTH_PAGE: usize = 6;
+pub const GAMES_PAGE: usize = 7;
+pub const ACHIEVEMENTS_PAGE: usize = 8;
+pub const HIDDEN_PAGE: usize = 9;
+
+#[derive(Debug)]
+pub enum Kind {
+    Directory(&'static [Node]),
+    Text(&'static str),
+    /// A file made up on the fly by the site, such as the achievements.
+    Generated {
+        size: usize,
+    },
+    /// Anything `cat` would garble, described the way `file` would.
+    Binary {
+        size: usize,
+        description: &'static str,
+    },
+}
+
+#[derive(Debug)]
+pub struct Node {
+    pub name: &'static str,
+    pub kind: Kind,
+    pub mode: &'static str,
+    pub modified: &'static str,
+    /// The menu page showing this node, if any.
+    pub page: Option<usize>,
+}
+
+const fn directory(name: &'static str, modified: &'static str, children: &'static [Node]) -> Node {
+    Node {
+        name,
+        kind: Kind::Directory(children),
+        mode: "drwxr-xr-x",
+        modified,
+        page: None,
+    }
+}
+
+const fn file(name: &'static str, modified: &'static str, text: &'static str) -> Node {
+    Node {
+        name,
+        kind: Kind::Text(text),
+        mode: "-rw-r--r--",
+        modified,
+        page: None,
+    }
+}
+
+const fn executable(name: &'static str, modified: &'static str, size: usize) -> Node {
+    Node {
+        name,
+        kind: Kind::Binary {
+            size,
+            description: "ELF 64-bit LSB pie executable, x86-64, version 1 (SYSV), \
+                dynamically linked, interpreter /lib64/ld-linux-x86-64.so.2, not stripped",
+        },
+        mode: "-rwxr-xr-x",
+        modified,
+        page: None,
+    }
+}
+
+impl Node {
+    const fn page(mut self, page: usize) -> Self {
+        self.page = Some(page);
+        self
+    }
+
+    const fn mode(mut self, mode: &'static str) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub const fn is_directory(&self) -> bool {
+        matches!(self.kind, Kind::Directory(_))
+    }
+
+    pub const fn is_hidden(&self) -> bool {
+        matches!(self.name.as_bytes().first(), Some(b'.'))
+    }
+
+    pub const fn children(&self) -> &'static [Node] {
+        match self.kind {
+            Kind::Directory(children) => children,
+            _ => &[],
+        }
+    }
+
+    /// The subdirectories, which the menu lists under the page of their
+    /// directory.
+    pub fn subpages(&self) -> impl Iterator<Item = &'static Node> {
+        self.children().iter().filter(|child| child.is_directory())
+    }
+
+    pub fn size(&self) -> usize {
+        match self.kind {
+            Kind::Directory(_) => DIRECTORY_SIZE,
+            Kind::Text(text) => text.len(),
+            Kind::Generated { size } | Kind::Binary { size, .. } => size,
+        }
+    }
+
+    /// What the node reads as: a text file's text, and the text files right
+    /// inside a directory one after another, the way its page shows them.
+    /// Subdirectories have pages of their own.
+    pub fn text(&self) -> Option<String> {
+        match self.kind {
+            Kind::Text(text) => Some(text.to_string()),
+            Kind::Directory(children) => {
+                let parts: Vec<String> = children
+                    .iter()
+                    .filter(|child| !child.is_directory())
+                    .filter_map(Node::text)
+                    .collect();
+                (!parts.is_empty()).then(|| parts.join("\n\n"))
+            }
+            Kind::Generated { .. } | Kind::Binary { .. } => None,
+        }
+    }
+}
+
+pub const ROOT: Node = directory(
+    USER,
+    "Jun 11 23:58",
+    &[
+        executable("tarbetu", "Jun 11 23:58", 1_337_126).page(ABOUT_PAGE),
+        directory(
+            "portfolio",
+            "May 30 02:14",
+            &[
+                file("README", "May 30 02:14", text::PORTFOLIO),
+                directory(
+                    "website",
+                    "Jun 11 23:41",
+                    &[file("README", "Jun 11 23:41", text::PORTFOLIO_WEBSITE)],
+                ),
+                directory(
+                    "tarlox",
+                    "Feb  2  2024",
+                    &[file("README", "Feb  2  2024", text::PORTFOLIO_TARLOX)],
+                ),
+                directory(
+                    "ducktongue",
+                    "Sep 17  2023",
+                    &[file("README", "Sep 17  2023", text::PORTFOLIO_DUCKTONGUE)],
+                ),
+                file("SEE_ALSO", "May 30 02:14", text::PORTFOLIO_SEE_ALSO),
+            ],
+        )
+        .page(PORTFOLIO_PAGE),
+        directory(
+            "translations",
+            "Mar  3  2023",
+            &[
+                file("README", "Mar  3  2023", text::TRANSLATIONS),
+                directory(
+                    "gentle-intro",
+                    "Jan 21  2023",
+                    &[file(
+                        "README",
+                        "Jan 21  2023",
+                        text::TRANSLATION_GENTLE_INTRO,
+                    )],
+                ),
+                directory(
+                    "rpm_paketleme",
+                    "Mar  3  2023",
+                    &[file("README", "Mar  3  2023", text::TRANSLATION_RPM)],
+                ),
+            ],
+        )
+        .page(TRANSLATIONS_PAGE),
+        file("lycian", "Apr 19 16:20", text::LYCIAN_PROJECT).page(LYCIAN_PAGE),
+        Node {
+            kind: Kind::Generated { size: 6_144 },
+            ..file("personal_soundtrack", "Jun  8 01:07", "")
+        }
+        .page(MUSIC_PAGE),
+        file("echoes_from_my_mania", "Oct 31  2022", text::ECHOES).page(ECHOES_PAGE),
+        file("kara_tilki_hiyerarsisi", "Dec 24  2021", text::KTH).page(KTH_PAGE),
+        directory(
+            "games",
+            "Jun  9 22:30",
+            &[
+                executable("2048", "Jun  9 22:30", 48_204),
+                executable("snake", "Jun  9 22:30", 51_966),
+                executable("tetris", "Jun  9 22:30", 73_728),
+            ],
+        )
+        .page(GAMES_PAGE),
+        Node {
+            kind: Kind::Generated { size: 1_024 },
+            ..file("achievements", "Jun 10 19:12", "")
+        }
+        .page(ACHIEVEMENTS_PAGE),
+        file(".bash_history", "Jun 11 23:59", text::BASH_HISTORY)
+            .mode("-rw-------")
+            .page(HIDDEN_PAGE),
+        Node {
+            kind: Kind::Binary {
+                size: 2_621_440,
+                description: "ELF 64-bit LSB core file, x86-64, version 1 (SYSV), \
+                    SVR4-style, from './tarbetu', real uid: 1000, execfn: './tarbetu'",
+            },
+            ..file("core", "Jun 11 23:58", "")
+        }
+        .mode("-rw-------"),
+    ],
+);
+
+/// Every page, including the ones hidden behind secrets.
+pub const PAGES: usize = {
+    let nodes = ROOT.children();
+    let mut seen = [false; 64];
+    let mut count = 0;
+    let mut index = 0;
+
+    while index < nodes.len() {
+        if let Some(page) = nodes[index].page {
+            assert!(
+                page < seen.len() && !seen[page],
+                "menu positions must be unique"
+            );
+            seen[page] = true;
+            count += 1;
+        }
+        index += 1;
+    }
+
+    let mut page = 0;
+    while page < count {
+        assert!(seen[page], "menu positions must have no gaps");
+        page += 1;
+    }
+    assert!(HIDDEN_PAGE == count - 1, "the hidden page must come last");
+    count
+};
+
+/// How many subpages the pages of the home directory have between them.
+pub const SUBPAGES: usize = {
+    let nodes = ROOT.children();
+    let mut count = 0;
+    let mut index = 0;
+
+    while index < nodes.len() {
+        let children = nodes[index].children();
+        let mut child = 0;
+
+        while nodes[index].page.is_some() && child < children.len() {
+            if children[child].is_directory() {
+                count += 1;
+            }
+            child += 1;
+        }
+        index += 1;
+    }
+    count
+};
+
+/// The nodes with a page, in menu order.
+pub fn pages() -> Vec<&'static Node> {
+    let mut pages: Vec<&Node> = ROOT
+        .children()
+        .iter()
+        .filter(|node| node.page.is_some())
+        .collect();
+    pages.sort_by_key(|node| node.page);
+    pages
+}
+
+/// Splits a path into the names it walks through from the home directory.
+/// `.`, `~`, the full home path and a leading `./` all mean home, while
+/// other absolute paths and other users' `~name` lead nowhere.
+fn components(path: &str) -> Option<Vec<&str>> {
+    let path = path.trim();
+    let home = [HOME, "~"]
+        .into_iter()
+        .find_map(|home| match path.strip_prefix(home)? {
+            "" => Some(""),
+            rest => rest.strip_prefix('/'),
+        });
+
+    let relative = match home {
+        Some(rest) => rest,
+        None if path.starts_with(['/', '~']) => return None,
+        None => path,
+    };
+
+    let mut components = Vec::new();
+    for name in relative.split('/') {
+        match name {
+            "" | "." => {}
+            ".." => {
+                components.pop()?;
+            }
+            name => components.push(name),
+        }
+    }
+    Some(components)
+}
+
+pub fn resolve(path: &str) -> Option<&'static Node> {
+    components(path)?.into_iter().try_fold(&ROOT, |node, name| {
+        node.children().iter().find(|child| child.name == name)
+    })
+}
+
+/// Short routes to pages that live deeper in the tree, as if linked from
+/// the home directory.
+pub const ALIASES: &[(&str, &str)] = &[("./ducktongue", "./portfolio/ducktongue")];
+
+/// The entry a route points at: the page of the deepest node along the
+/// path that has one, or the subpage right below it.
+pub fn route(path: &str) -> Option<Entry> {
+    let path = ALIASES
+        .iter()
+        .find(|(alias, _)| components(alias) == components(path))
+        .map_or(path, |(_, target)| target);
+
+    let mut node = &ROOT;
+    let mut entry = None;
+
+    for name in components(path)? {
+        let parent = node;
+        node = node.children().iter().find(|child| child.name == name)?;
+
+        entry = match (node.page, entry) {
+            (Some(page), _) => Some(Entry::page(page)),
+            (
+                None,
+                Some(Entry {
+                    page,
+                    subpage: None,
+                }),
+            ) if parent.page == Some(page) => Some(Entry {
+                page,
+                subpage: parent.subpages().position(|subpage| subpage.name == name),
+            }),
+            (None, entry) => entry,
+        };
+    }
+    entry
+}
+
+/// The route in the address bar, as in `#./portfolio/tarlox`.
+pub fn location_route() -> Option<String> {
+    let hash = web_sys::window()?.location().hash().ok()?;
+    let route = hash.strip_prefix('#')?;
+
+    (!route.is_empty()).then(|| route.to_string())
+}
+
+/// Puts the route of a page in the address bar without adding to the
+/// browser history.
+pub fn set_location_route(route: &str) {
+    let Some(history) = web_sys::window().and_then(|window| window.history().ok()) else {
+        return;
+    };
+
+    let _ = history.replace_state_with_url(
+        &web_sys::wasm_bindgen::JsValue::NULL,
+        "",
+        Some(&format!("#{route}")),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn home_can_be_spelled_many_ways() {
+        for path in [".", "./", "~", "~/", HOME, "/home/tarbetu/", "portfolio/.."] {
+            assert_eq!(resolve(path).map(|node| node.name), Some(USER), "{path}");
+        }
+    }
+
+    #[test]
+    fn paths_walk_down_from_home() {
+        let readme = resolve("~/portfolio/tarlox/README").unwrap();
+        assert_eq!(readme.text().as_deref(), Some(text::PORTFOLIO_TARLOX));
+
+        assert_eq!(resolve("./games/../games/snake").unwrap().name, "snake");
+        assert_eq!(
+            resolve("/home/tarbetu/lycian").unwrap().page,
+            Some(LYCIAN_PAGE)
+        );
+        assert!(resolve("portfolio/nothing").is_none());
+    }
+
+    #[test]
+    fn only_home_is_home() {
+        for path in [
+            "/home/tarbetufoo",
+            "/home/tarbetufoo/lycian",
+            "~lycian",
+            "~foo/lycian",
+            "/etc",
+            "..",
+        ] {
+            assert!(resolve(path).is_none(), "{path}");
+            assert_eq!(route(path), None, "{path}");
+        }
+    }
+
+    #[test]
+    fn routes_point_at_the_deepest_page() {
+        assert_eq!(route("./lycian"), Some(Entry::page(LYCIAN_PAGE)));
+        assert_eq!(route("./games/tetris"), Some(Entry::page(GAMES_PAGE)));
+        assert_eq!(
+            route("./portfolio/tarlox/README"),
+            Some(Entry {
+                page: PORTFOLIO_PAGE,
+                subpage: Some(1),
+            })
+        );
+        assert_eq!(route("."), None);
+    }
+
+    #[test]
+    fn aliases_route_to_their_targets() {
+        for (alias, target) in ALIASES {
+            assert!(route(target).is_some(), "{target}");
+            assert_eq!(route(alias), route(target), "{alias}");
+        }
+        assert_eq!(route("~/ducktongue"), route("./portfolio/ducktongue"));
+    }
+
+    #[test]
+    fn pages_come_in_menu_order() {
+        let pages = pages();
+
+        assert_eq!(pages.len(), PAGES);
+        assert!(pages
+            .iter()
+            .enumerate()
+            .all(|(index, node)| node.page == Some(index)));
+        assert_eq!(pages[HIDDEN_PAGE].name, ".bash_history");
+    }
+}